@@ -0,0 +1,96 @@
+use adventofcode::{
+    days::{
+        day01::Day01,
+        day02::{self, Day02},
+        day03::Day03,
+        day04::Day04,
+        day05::Day05,
+        day06::Day06,
+        day07::Day07,
+        day08::Day08,
+        day09::Day09,
+        Solution,
+    },
+    input,
+};
+use std::{
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+// Each benchmark runs for at least this long after a short warm up
+const MEASURE: Duration = Duration::from_millis(500);
+const WARM_UP: Duration = Duration::from_millis(100);
+
+struct Bencher {
+    filters: Vec<String>,
+}
+
+impl Bencher {
+    fn bench<T>(&self, name: &str, mut routine: impl FnMut() -> T) {
+        if !self.filters.is_empty() && !self.filters.iter().any(|f| name.contains(&f[..])) {
+            return;
+        }
+
+        let start = Instant::now();
+        while start.elapsed() < WARM_UP {
+            black_box(routine());
+        }
+
+        let mut samples = vec![];
+        let start = Instant::now();
+        while start.elapsed() < MEASURE || samples.len() < 10 {
+            let sample = Instant::now();
+            black_box(routine());
+            samples.push(sample.elapsed());
+        }
+        samples.sort();
+
+        let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+        println!(
+            "{:<20} mean {:>12?}  median {:>12?}  min {:>12?}  ({} runs)",
+            name,
+            mean,
+            samples[samples.len() / 2],
+            samples[0],
+            samples.len()
+        );
+    }
+
+    fn solution<S: Solution>(&self, day: u32) {
+        let raw = input::embedded(day).expect("no checked-in input");
+        let parsed = S::parse(raw).expect("checked-in input should parse");
+
+        self.bench(&format!("day{:02}/parse", day), || S::parse(black_box(raw)));
+        self.bench(&format!("day{:02}/part1", day), || {
+            S::part1(black_box(&parsed))
+        });
+        self.bench(&format!("day{:02}/part2", day), || {
+            S::part2(black_box(&parsed))
+        });
+    }
+}
+
+fn main() {
+    // cargo passes --bench along with any filters
+    let bencher = Bencher {
+        filters: env::args()
+            .skip(1)
+            .filter(|a| !a.starts_with("--"))
+            .collect(),
+    };
+
+    bencher.solution::<Day01>(1);
+    bencher.solution::<Day02>(2);
+    bencher.bench("day02/parse_regex", || {
+        day02::parse_regex(black_box(input::embedded(2).unwrap()))
+    });
+    bencher.solution::<Day03>(3);
+    bencher.solution::<Day04>(4);
+    bencher.solution::<Day05>(5);
+    bencher.solution::<Day06>(6);
+    bencher.solution::<Day07>(7);
+    bencher.solution::<Day08>(8);
+    bencher.solution::<Day09>(9);
+}
@@ -0,0 +1,205 @@
+use crate::{
+    days::{self, Part},
+    error::AocError,
+    util,
+};
+use serde::Deserialize;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// Where the puzzle inputs, examples and `answers.toml` are checked in.
+pub fn data_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("data")
+}
+
+/// Expected answers for each day and input, read from `answers.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "answer", default)]
+    pub answers: Vec<Entry>,
+}
+
+/// The answers for one day on one input, `input` relative to the manifest.
+/// A part is left out when the input has no answer for it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Entry {
+    pub day: u32,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Entry {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+impl Manifest {
+    pub fn load(path: impl AsRef<Path>) -> Result<Manifest, AocError> {
+        let path = path.as_ref();
+        toml::from_str(&util::read_file(path)?).map_err(|err| AocError::Config {
+            path: Some(path.to_path_buf()),
+            reason: err.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// Answered, but the manifest has nothing for it yet.
+    NewlySolved {
+        actual: String,
+    },
+    /// The manifest has an answer the solution doesn't produce.
+    Missing {
+        expected: String,
+    },
+    /// The input couldn't be read or parsed.
+    Failed {
+        reason: String,
+    },
+}
+
+/// The outcome for one part of one day on one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub day: u32,
+    pub input: String,
+    pub part: Part,
+    pub status: Status,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.status == Status::Pass
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {} part {} {}: ", self.day, self.part, self.input)?;
+        match &self.status {
+            Status::Pass => write!(f, "ok"),
+            Status::Mismatch { expected, actual } => {
+                write!(f, "expected {}, got {}", expected, actual)
+            }
+            Status::NewlySolved { actual } => write!(f, "newly solved, {} isn't recorded", actual),
+            Status::Missing { expected } => write!(f, "expected {}, got no answer", expected),
+            Status::Failed { reason } => write!(f, "{}", reason),
+        }
+    }
+}
+
+fn check_entry(entry: &Entry, dir: &Path) -> Vec<Check> {
+    let check = |part, status| Check {
+        day: entry.day,
+        input: entry.input.clone(),
+        part,
+        status,
+    };
+    let expected_only = |reason: &str| {
+        Part::BOTH
+            .iter()
+            .map(|&part| match entry.expected(part) {
+                Some(expected) => check(
+                    part,
+                    Status::Missing {
+                        expected: String::from(expected),
+                    },
+                ),
+                None => check(
+                    part,
+                    Status::Failed {
+                        reason: String::from(reason),
+                    },
+                ),
+            })
+            .collect()
+    };
+
+    let day = match days::get(entry.day) {
+        Some(day) => day,
+        None => return expected_only("day isn't registered"),
+    };
+
+    let report =
+        util::read_file(dir.join(&entry.input)).and_then(|input| day.run(&input, Part::BOTH));
+    let report = match report {
+        Ok(report) => report,
+        Err(err) => {
+            let reason = err.to_string();
+            return Part::BOTH
+                .iter()
+                .map(|&part| {
+                    check(
+                        part,
+                        Status::Failed {
+                            reason: reason.clone(),
+                        },
+                    )
+                })
+                .collect();
+        }
+    };
+
+    report
+        .answers
+        .into_iter()
+        .filter_map(|answer| {
+            let solved = answer.value != days::NO_ANSWER;
+            let status = match entry.expected(answer.part) {
+                Some(expected) if expected == answer.value => Status::Pass,
+                Some(expected) if !solved => Status::Missing {
+                    expected: String::from(expected),
+                },
+                Some(expected) => Status::Mismatch {
+                    expected: String::from(expected),
+                    actual: answer.value,
+                },
+                None if solved => Status::NewlySolved {
+                    actual: answer.value,
+                },
+                None => return None,
+            };
+            Some(check(answer.part, status))
+        })
+        .collect()
+}
+
+/// Run every entry of `manifest`, with inputs relative to `dir`, plus any
+/// registered day whose `dayN.txt` has no entry yet.
+pub fn verify(manifest: &Manifest, dir: &Path) -> Vec<Check> {
+    let mut entries = manifest.answers.clone();
+
+    for day in days::DAYS {
+        let input = format!("day{}.txt", day.number);
+        let recorded = entries
+            .iter()
+            .any(|entry| entry.day == day.number && entry.input == input);
+
+        if !recorded && dir.join(&input).exists() {
+            entries.push(Entry {
+                day: day.number,
+                input,
+                part1: None,
+                part2: None,
+            });
+        }
+    }
+
+    entries
+        .iter()
+        .flat_map(|entry| check_entry(entry, dir))
+        .collect()
+}
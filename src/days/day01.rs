@@ -1,49 +1,323 @@
-// Probably should've done this https://en.wikipedia.org/wiki/3SUM
+//! Day 1: Report Repair, find the entries that sum to 2020.
+//!
+//! ```
+//! use adventofcode::days::{day01::Day01, Solution};
+//!
+//! let expenses = Day01::parse("1721\n979\n366\n299\n675\n1456").unwrap();
+//! assert_eq!(Day01::part1(&expenses), Some(514579));
+//! assert_eq!(Day01::part2(&expenses), Some(241861950));
+//! ```
 
-pub fn part1(data: Vec<u32>) -> Result<u32, ()> {
-    for i in 0..data.len() {
-        for ii in 0..data.len() {
-            let inum = data[i];
-            let iinum = data[ii];
-            if inum + iinum == 2020 {
-                return Ok(inum * iinum);
+// Two entries use a hash set, more than two sort and walk two pointers in from
+// the ends for the last pair, see https://en.wikipedia.org/wiki/3SUM
+
+use super::Solution;
+use crate::error::AocError;
+use crate::util;
+use std::collections::HashMap;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<u32>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u32>, AocError> {
+        util::parse_lines(input)
+    }
+
+    fn part1(input: &Vec<u32>) -> Option<u64> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<u32>) -> Option<u64> {
+        part2(input)
+    }
+}
+
+/// How [`find_k_sum_with`] picks entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SumOptions {
+    /// Let the same entry be used more than once in a sum.
+    pub allow_reuse: bool,
+    /// Return every combination instead of stopping at the first.
+    pub all_solutions: bool,
+}
+
+/// Entries that add up to the target, `indices` ascending and `values` in the same order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KSum {
+    pub indices: Vec<usize>,
+    pub values: Vec<u32>,
+}
+
+impl KSum {
+    fn from_indices(data: &[u32], mut indices: Vec<usize>) -> KSum {
+        indices.sort_unstable();
+        let values = indices.iter().map(|&i| data[i]).collect();
+        KSum { indices, values }
+    }
+
+    pub fn product(&self) -> u64 {
+        self.values.iter().map(|&v| v as u64).product()
+    }
+}
+
+/// First `k` different entries of `data` that sum to `target`.
+pub fn find_k_sum(data: &[u32], k: usize, target: u32) -> Option<KSum> {
+    find_k_sum_with(data, k, target, SumOptions::default())
+        .into_iter()
+        .next()
+}
+
+/// Combinations of `k` entries of `data` that sum to `target`. Each combination
+/// is returned once, whatever order its entries could be picked in.
+pub fn find_k_sum_with(data: &[u32], k: usize, target: u32, options: SumOptions) -> Vec<KSum> {
+    let mut found = vec![];
+
+    match k {
+        0 => {
+            if target == 0 {
+                found.push(vec![]);
             }
         }
+        1 => {
+            for (i, &value) in data.iter().enumerate() {
+                if value == target {
+                    found.push(vec![i]);
+                    if !options.all_solutions {
+                        break;
+                    }
+                }
+            }
+        }
+        2 => two_sum_hashed(data, target as u64, options, &mut found),
+        _ => {
+            let mut sorted: Vec<(u64, usize)> = data
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| (value as u64, i))
+                .collect();
+            sorted.sort_unstable();
+
+            k_sum_sorted(
+                &sorted,
+                0,
+                k,
+                target as u64,
+                options,
+                &mut vec![],
+                &mut found,
+            );
+        }
     }
-    Err(())
+
+    found
+        .into_iter()
+        .map(|indices| KSum::from_indices(data, indices))
+        .collect()
 }
 
-pub fn part2(data: Vec<u32>) -> Result<u32, ()> {
-    for i in 0..data.len() {
-        for ii in 0..data.len() {
-            for iii in 0..data.len() {
-                let inum = data[i];
-                let iinum = data[ii];
-                let iiinum = data[iii];
+fn two_sum_hashed(data: &[u32], target: u64, options: SumOptions, found: &mut Vec<Vec<usize>>) {
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+
+    for (i, &value) in data.iter().enumerate() {
+        let value = value as u64;
+        if value > target {
+            continue;
+        }
 
-                if inum + iinum + iiinum == 2020 {
-                    return Ok(inum * iinum * iiinum);
+        seen.entry(value).or_default().push(i);
+
+        for &other in seen.get(&(target - value)).into_iter().flatten() {
+            if other == i && !options.allow_reuse {
+                continue;
+            }
+            found.push(vec![other, i]);
+            if !options.all_solutions {
+                return;
+            }
+        }
+    }
+}
+
+/// Sums of `k` entries from `sorted[start..]`, appending each to `chosen` as it goes.
+/// Returns true once it's found all it was asked for.
+fn k_sum_sorted(
+    sorted: &[(u64, usize)],
+    start: usize,
+    k: usize,
+    target: u64,
+    options: SumOptions,
+    chosen: &mut Vec<usize>,
+    found: &mut Vec<Vec<usize>>,
+) -> bool {
+    if k == 2 {
+        return two_sum_pointers(sorted, start, target, options, chosen, found);
+    }
+
+    for first in start..sorted.len() {
+        let (value, index) = sorted[first];
+        if value > target {
+            break;
+        }
+
+        chosen.push(index);
+        let next = if options.allow_reuse {
+            first
+        } else {
+            first + 1
+        };
+        let done = k_sum_sorted(sorted, next, k - 1, target - value, options, chosen, found);
+        chosen.pop();
+
+        if done {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn two_sum_pointers(
+    sorted: &[(u64, usize)],
+    start: usize,
+    target: u64,
+    options: SumOptions,
+    chosen: &[usize],
+    found: &mut Vec<Vec<usize>>,
+) -> bool {
+    if start >= sorted.len() {
+        return false;
+    }
+
+    let mut push = |a: usize, b: usize| {
+        let mut indices = chosen.to_vec();
+        indices.push(sorted[a].1);
+        indices.push(sorted[b].1);
+        found.push(indices);
+        !options.all_solutions
+    };
+
+    let (mut low, mut high) = (start, sorted.len() - 1);
+
+    while low < high || (options.allow_reuse && low == high) {
+        let sum = sorted[low].0 + sorted[high].0;
+
+        if sum < target {
+            low += 1;
+        } else if sum > target {
+            if high == 0 {
+                break;
+            }
+            high -= 1;
+        } else if sorted[low].0 == sorted[high].0 {
+            // Everything between the pointers is the same value, so any pair of them works
+            for a in low..=high {
+                let first_b = if options.allow_reuse { a } else { a + 1 };
+                for b in first_b..=high {
+                    if push(a, b) {
+                        return true;
+                    }
+                }
+            }
+            break;
+        } else {
+            let low_end = (low..high)
+                .find(|&i| sorted[i].0 != sorted[low].0)
+                .unwrap_or(high);
+            let high_start = (low_end..=high)
+                .rev()
+                .find(|&i| sorted[i].0 != sorted[high].0)
+                .map_or(low_end, |i| i + 1);
+
+            for a in low..low_end {
+                for b in high_start..=high {
+                    if push(a, b) {
+                        return true;
+                    }
                 }
             }
+
+            low = low_end;
+            if high_start == 0 {
+                break;
+            }
+            high = high_start - 1;
         }
     }
-    Err(())
+
+    false
+}
+
+/// Product of the two entries that sum to 2020.
+pub fn part1(data: &[u32]) -> Option<u64> {
+    find_k_sum(data, 2, 2020).map(|sum| sum.product())
+}
+
+/// Product of the three entries that sum to 2020.
+pub fn part2(data: &[u32]) -> Option<u64> {
+    find_k_sum(data, 3, 2020).map(|sum| sum.product())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util;
+    use crate::input;
 
     #[test]
     fn d1part1test() {
-        let data = util::lines_from_file_as_u32("./data/day1.txt");
-        assert_eq!(1007104, part1(data).unwrap())
+        let data = Day01::parse(input::embedded(1).unwrap()).unwrap();
+        assert_eq!(1007104, part1(&data).unwrap())
     }
 
     #[test]
     fn d1part2test() {
-        let data = util::lines_from_file_as_u32("./data/day1.txt");
-        assert_eq!(18847752, part2(data).unwrap())
+        let data = Day01::parse(input::embedded(1).unwrap()).unwrap();
+        assert_eq!(18847752, part2(&data).unwrap())
+    }
+
+    #[test]
+    fn d1reusetest() {
+        let data = [1010, 5, 2015];
+        assert_eq!(
+            Some(vec![1, 2]),
+            find_k_sum(&data, 2, 2020).map(|sum| sum.indices)
+        );
+
+        let options = SumOptions {
+            allow_reuse: true,
+            all_solutions: true,
+        };
+        let found: Vec<Vec<usize>> = find_k_sum_with(&data, 2, 2020, options)
+            .into_iter()
+            .map(|sum| sum.indices)
+            .collect();
+        assert_eq!(vec![vec![0, 0], vec![1, 2]], found);
+    }
+
+    #[test]
+    fn d1allsolutionstest() {
+        let data = [1, 2, 3, 4, 5, 5];
+        let options = SumOptions {
+            allow_reuse: false,
+            all_solutions: true,
+        };
+
+        let indices = |k, target| {
+            let mut found: Vec<Vec<usize>> = find_k_sum_with(&data, k, target, options)
+                .into_iter()
+                .map(|sum| sum.indices)
+                .collect();
+            found.sort();
+            found
+        };
+
+        assert_eq!(
+            vec![vec![0, 3, 4], vec![0, 3, 5], vec![1, 2, 4], vec![1, 2, 5]],
+            indices(3, 10)
+        );
+        assert_eq!(vec![vec![0, 3, 4, 5], vec![1, 2, 4, 5]], indices(4, 15));
     }
 }
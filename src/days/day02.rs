@@ -1,81 +1,435 @@
+//! Day 2: Password Philosophy, check passwords against their policies.
+//!
+//! ```
+//! use adventofcode::days::day02::{PolicyKind, Validator};
+//! use adventofcode::PasswordEntry;
+//!
+//! let entry = PasswordEntry::parse("1-3 a: abcde").unwrap();
+//! assert_eq!(entry.password, "abcde");
+//! assert_eq!(adventofcode::days::day02::part1(&[entry.clone()]), 1);
+//! assert!(PolicyKind::AllPositions.validate(&entry.policy, entry.password).is_err());
+//! ```
+
+use super::Solution;
+use crate::error::AocError;
 use regex::Regex;
+use std::{collections::HashSet, fmt};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<PasswordEntry<'a>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<PasswordEntry<'_>>, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| parse_line(index + 1, line))
+            .collect()
+    }
+
+    fn part1(input: &Vec<PasswordEntry<'_>>) -> Option<u32> {
+        Some(part1(input))
+    }
+
+    fn part2(input: &Vec<PasswordEntry<'_>>) -> Option<u32> {
+        Some(part2(input))
+    }
+}
+
+/// The `num1-num2 character` half of a line. What the numbers mean is up to
+/// the [`Validator`] checking it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    pub num1: usize,
+    pub num2: usize,
+    pub character: char,
+}
+
+impl PasswordPolicy {
+    // 1-indexed, so 0 is never a match
+    fn char_at(&self, password: &str, pos: usize) -> bool {
+        let i = match pos.checked_sub(1) {
+            Some(i) => i,
+            None => return false,
+        };
+
+        // Byte and character positions agree up to the first multi-byte character
+        let bytes = password.as_bytes();
+        if self.character.is_ascii() && bytes[..i.min(bytes.len())].is_ascii() {
+            return bytes.get(i) == Some(&(self.character as u8));
+        }
+        password.chars().nth(i) == Some(self.character)
+    }
+}
+
+impl fmt::Display for PasswordPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{} {}", self.num1, self.num2, self.character)
+    }
+}
+
+/// One line of the password database, `num1-num2 character: password`,
+/// borrowing the password from the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordEntry<'a> {
+    pub policy: PasswordPolicy,
+    pub password: &'a str,
+}
+
+impl<'a> PasswordEntry<'a> {
+    pub fn parse(line: &'a str) -> Result<PasswordEntry<'a>, AocError> {
+        parse_line(1, line)
+    }
+}
+
+impl fmt::Display for PasswordEntry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.policy, self.password)
+    }
+}
+
+const EXPECTED_LINE: &str = "expected \"min-max c: password\"";
+
+/// One line of the database, walking its bytes without allocating. The
+/// separators are ASCII, the policy character can be any UTF-8 character.
+pub fn parse_line(line_number: usize, line: &str) -> Result<PasswordEntry<'_>, AocError> {
+    let malformed = || AocError::parse(line_number, line, EXPECTED_LINE);
+    let bytes = line.as_bytes();
+    let digits_end = |start: usize| {
+        start
+            + bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+    };
+    let is_space = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_whitespace);
+
+    let num1_end = digits_end(0);
+    if bytes.get(num1_end) != Some(&b'-') {
+        return Err(malformed());
+    }
+    let num2_end = digits_end(num1_end + 1);
+    if !is_space(num2_end) {
+        return Err(malformed());
+    }
+
+    // Only ASCII so far, so this is a character boundary
+    let character = line[num2_end + 1..].chars().next().ok_or_else(malformed)?;
+    let colon = num2_end + 1 + character.len_utf8();
+    if bytes.get(colon) != Some(&b':') || !is_space(colon + 1) {
+        return Err(malformed());
+    }
+
+    let bound = |digits: &str| {
+        digits
+            .parse::<usize>()
+            .map_err(|_| AocError::parse(line_number, line, "policy bound is not a number"))
+    };
+
+    Ok(PasswordEntry {
+        policy: PasswordPolicy {
+            num1: bound(&line[..num1_end])?,
+            num2: bound(&line[num1_end + 1..num2_end])?,
+            character,
+        },
+        password: &line[colon + 2..],
+    })
+}
+
+/// The whole database parsed with a regex, the way it used to be. Kept to
+/// benchmark [`parse_line`] against.
+pub fn parse_regex(input: &str) -> Result<Vec<PasswordEntry<'_>>, AocError> {
+    let re = Regex::new(r"^([0-9]*)-([0-9]*)\s(.):\s(.*)$").unwrap();
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_line_regex(&re, index + 1, line))
+        .collect()
+}
 
-fn parse_line(line: &str) -> (usize, usize, &str, &str) {
-    let cap = Regex::new(r"^([0-9]*)-([0-9]*)\s(.):\s(.*)$")
-        .unwrap()
+fn parse_line_regex<'a>(
+    re: &Regex,
+    line_number: usize,
+    line: &'a str,
+) -> Result<PasswordEntry<'a>, AocError> {
+    let cap = re
         .captures(line)
-        .unwrap();
+        .ok_or_else(|| AocError::parse(line_number, line, EXPECTED_LINE))?;
 
-    let num1 = cap
-        .get(1)
-        .map_or("", |m| m.as_str())
-        .parse::<usize>()
-        .unwrap();
+    let parse_number = |index| {
+        cap.get(index)
+            .map_or("", |m| m.as_str())
+            .parse::<usize>()
+            .map_err(|_| AocError::parse(line_number, line, "policy bound is not a number"))
+    };
 
-    let num2 = cap
-        .get(2)
-        .map_or("", |m| m.as_str())
-        .parse::<usize>()
-        .unwrap();
+    let num1 = parse_number(1)?;
+    let num2 = parse_number(2)?;
 
-    let character = cap.get(3).map_or("", |m| m.as_str());
+    // The regex only matches a single character here
+    let character = cap[3].chars().next().unwrap();
     let password = cap.get(4).map_or("", |m| m.as_str());
 
-    return (num1, num2, character, password);
+    Ok(PasswordEntry {
+        policy: PasswordPolicy {
+            num1,
+            num2,
+            character,
+        },
+        password,
+    })
 }
 
-pub fn part1(data: Vec<String>) -> u32 {
-    let mut total = 0;
+/// A rule a password has to follow under its policy.
+pub trait Validator {
+    /// Short name for the rule, used in reports.
+    fn name(&self) -> &str;
 
-    for item in &data {
-        let (min, max, character, password) = parse_line(item);
-        let count = password.matches(character).count();
+    /// `Err` says why `password` breaks the rule.
+    fn validate(&self, policy: &PasswordPolicy, password: &str) -> Result<(), String>;
+}
+
+/// The ways of reading a [`PasswordPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyKind {
+    /// The character appears between `num1` and `num2` times.
+    CountRange,
+    /// The character is at exactly one of the 1-indexed positions `num1` and `num2`.
+    ExactlyOnePosition,
+    /// The character is at both positions `num1` and `num2`.
+    AllPositions,
+    /// The character doesn't appear at all.
+    ForbiddenCharacter,
+    /// The password has at least `num1` different characters.
+    MinDistinct,
+}
+
+impl PolicyKind {
+    pub const ALL: &'static [PolicyKind] = &[
+        PolicyKind::CountRange,
+        PolicyKind::ExactlyOnePosition,
+        PolicyKind::AllPositions,
+        PolicyKind::ForbiddenCharacter,
+        PolicyKind::MinDistinct,
+    ];
+}
 
-        if count >= min && count <= max {
-            total = total + 1;
+impl Validator for PolicyKind {
+    fn name(&self) -> &str {
+        match self {
+            PolicyKind::CountRange => "count-range",
+            PolicyKind::ExactlyOnePosition => "exactly-one-position",
+            PolicyKind::AllPositions => "all-positions",
+            PolicyKind::ForbiddenCharacter => "forbidden-character",
+            PolicyKind::MinDistinct => "min-distinct",
         }
     }
 
-    return total;
+    fn validate(&self, policy: &PasswordPolicy, password: &str) -> Result<(), String> {
+        let c = policy.character;
+        let (num1, num2) = (policy.num1, policy.num2);
+
+        match self {
+            PolicyKind::CountRange => {
+                let count = password.chars().filter(|&p| p == c).count();
+                if count < num1 || count > num2 {
+                    return Err(format!(
+                        "{:?} appears {} times, expected {} to {}",
+                        c, count, num1, num2
+                    ));
+                }
+            }
+            PolicyKind::ExactlyOnePosition => {
+                match (
+                    policy.char_at(password, num1),
+                    policy.char_at(password, num2),
+                ) {
+                    (true, true) => {
+                        return Err(format!("{:?} is at both {} and {}", c, num1, num2))
+                    }
+                    (false, false) => {
+                        return Err(format!("{:?} is at neither {} nor {}", c, num1, num2))
+                    }
+                    _ => {}
+                }
+            }
+            PolicyKind::AllPositions => {
+                if let Some(pos) = [num1, num2]
+                    .iter()
+                    .find(|&&pos| !policy.char_at(password, pos))
+                {
+                    return Err(format!("{:?} is not at {}", c, pos));
+                }
+            }
+            PolicyKind::ForbiddenCharacter => {
+                if let Some(i) = password.chars().position(|p| p == c) {
+                    return Err(format!("{:?} is at {}", c, i + 1));
+                }
+            }
+            PolicyKind::MinDistinct => {
+                let distinct = password.chars().collect::<HashSet<_>>().len();
+                if distinct < num1 {
+                    return Err(format!(
+                        "{} different characters, expected at least {}",
+                        distinct, num1
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
-pub fn part2(data: Vec<String>) -> u32 {
-    let mut total = 0;
+/// An entry breaking one rule, `line` counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    pub line: usize,
+    pub entry: &'a PasswordEntry<'a>,
+    pub rule: String,
+    pub reason: String,
+}
 
-    for item in &data {
-        let (pos1, pos2, character, password) = parse_line(item);
+impl fmt::Display for Failure<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {}: {}: \"{}\"",
+            self.line, self.rule, self.reason, self.entry
+        )
+    }
+}
 
-        let char_1_correct = match password.chars().nth(pos1 - 1) {
-            Some(c) => c.to_string() == character,
-            None => false,
-        };
-        let char_2_correct = match password.chars().nth(pos2 - 1) {
-            Some(c) => c.to_string() == character,
-            None => false,
-        };
+/// Every rule in `validators` each entry breaks, in line order.
+pub fn failures<'a>(
+    entries: &'a [PasswordEntry<'a>],
+    validators: &[&dyn Validator],
+) -> Vec<Failure<'a>> {
+    let mut failures = vec![];
 
-        if char_1_correct != char_2_correct {
-            total = total + 1;
+    for (index, entry) in entries.iter().enumerate() {
+        for validator in validators {
+            if let Err(reason) = validator.validate(&entry.policy, entry.password) {
+                failures.push(Failure {
+                    line: index + 1,
+                    entry,
+                    rule: String::from(validator.name()),
+                    reason,
+                });
+            }
         }
     }
 
-    return total;
+    failures
+}
+
+/// Number of entries `validator` accepts.
+pub fn count_valid(data: &[PasswordEntry<'_>], validator: &dyn Validator) -> u32 {
+    data.iter()
+        .filter(|entry| validator.validate(&entry.policy, entry.password).is_ok())
+        .count() as u32
+}
+
+/// Entries whose character appears between `num1` and `num2` times.
+pub fn part1(data: &[PasswordEntry<'_>]) -> u32 {
+    count_valid(data, &PolicyKind::CountRange)
+}
+
+/// Entries whose character is at exactly one of the 1-indexed positions `num1` and `num2`.
+pub fn part2(data: &[PasswordEntry<'_>]) -> u32 {
+    count_valid(data, &PolicyKind::ExactlyOnePosition)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util;
+    use crate::input;
 
     #[test]
     fn d2part1test() {
-        let data = util::lines_from_file("./data/day2.txt");
-        assert_eq!(part1(data), 580);
+        let data = Day02::parse(input::embedded(2).unwrap()).unwrap();
+        assert_eq!(part1(&data), 580);
     }
 
     #[test]
     fn d2part2test() {
-        let data = util::lines_from_file("./data/day2.txt");
-        assert_eq!(part2(data), 611);
+        let data = Day02::parse(input::embedded(2).unwrap()).unwrap();
+        assert_eq!(part2(&data), 611);
+    }
+
+    #[test]
+    fn d2malformedtest() {
+        let err = Day02::parse("1-3 a: abcde\n1-x b: cdefg").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: expected \"min-max c: password\": \"1-x b: cdefg\""
+        );
+    }
+
+    #[test]
+    fn d2regextest() {
+        let input = input::embedded(2).unwrap();
+        assert_eq!(Day02::parse(input).unwrap(), parse_regex(input).unwrap());
+
+        for line in &[
+            "1-x b: cdefg",
+            "-3 a: abc",
+            "1-3 a:abc",
+            "1-3 : abc",
+            "1-3 a",
+        ] {
+            assert_eq!(
+                parse_line(1, line).map_err(|err| err.to_string()),
+                parse_regex(line)
+                    .map(|mut entries| entries.remove(0))
+                    .map_err(|err| err.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn d2utf8test() {
+        let data = Day02::parse("1-2 é: éaé\n2-3 ü: aüb\n1-1 ß: ßß\n1-2 a: éab").unwrap();
+
+        assert_eq!('é', data[0].policy.character);
+        assert_eq!("aüb", data[1].password);
+        assert_eq!(2, part1(&data));
+        assert_eq!(3, part2(&data));
+    }
+
+    #[test]
+    fn d2policykindstest() {
+        let data = Day02::parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+        let count = |kind| count_valid(&data, &kind);
+
+        assert_eq!(2, count(PolicyKind::CountRange));
+        assert_eq!(1, count(PolicyKind::ExactlyOnePosition));
+        assert_eq!(1, count(PolicyKind::AllPositions));
+        assert_eq!(1, count(PolicyKind::ForbiddenCharacter));
+        assert_eq!(2, count(PolicyKind::MinDistinct));
+    }
+
+    #[test]
+    fn d2failurestest() {
+        let data = Day02::parse("1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+        let report: Vec<String> = failures(
+            &data,
+            &[&PolicyKind::CountRange, &PolicyKind::ExactlyOnePosition],
+        )
+        .iter()
+        .map(|failure| failure.to_string())
+        .collect();
+
+        assert_eq!(
+            vec![
+                "line 1: count-range: 'b' appears 0 times, expected 1 to 3: \"1-3 b: cdefg\"",
+                "line 1: exactly-one-position: 'b' is at neither 1 nor 3: \"1-3 b: cdefg\"",
+                "line 2: exactly-one-position: 'c' is at both 2 and 9: \"2-9 c: ccccccccc\"",
+            ],
+            report
+        );
     }
 }
@@ -1,53 +1,180 @@
-pub struct Pos {
-    x: usize,
-    y: usize,
-}
+//! Day 3: Toboggan Trajectory, count the trees hit on a repeating map.
+//!
+//! ```
+//! use adventofcode::{traverse, util::Grid, Point};
+//!
+//! let map = Grid::from_chars("..#\n#..\n.#.").unwrap();
+//! assert_eq!(traverse(&map, Point::new(1, 1)), 0);
+//! assert_eq!(traverse(&map, Point::new(2, 1)), 1);
+//! ```
+//!
+//! Any slope can be tried, see [`slope_hits`] and [`render_path`].
 
-pub fn traverse(map: &Vec<Vec<char>>, inc: Pos) -> u32 {
-    let mut total = 0;
-    let mut pos = Pos { x: 0, y: 0 };
+use super::Solution;
+use crate::error::AocError;
+use crate::util::{Grid, Point};
+use std::ops::RangeInclusive;
 
-    while pos.y < map.len() {
-        if map[pos.y][pos.x % map[pos.y].len()] == '#' {
-            total += 1;
-        }
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Grid<char>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Grid<char>, AocError> {
+        Grid::from_chars(input)
+    }
+
+    fn part1(input: &Grid<char>) -> Option<u32> {
+        Some(part1(input))
+    }
 
-        pos.x += inc.x;
-        pos.y += inc.y;
+    fn part2(input: &Grid<char>) -> Option<u32> {
+        Some(part2(input))
     }
+}
+
+/// The slopes part 2 multiplies together, `x` across for every `y` down.
+pub const PUZZLE_SLOPES: [Point; 5] = [
+    Point::new(1, 1),
+    Point::new(3, 1),
+    Point::new(5, 1),
+    Point::new(7, 1),
+    Point::new(1, 2),
+];
+
+/// Every cell visited at slope `inc`, as `(x, y)` inside the map. Starts at
+/// the top left going down, or the bottom left going up when `inc.y` is
+/// negative, and the map repeats to either side. A slope with no `y` only
+/// visits the start.
+pub fn path(map: &Grid<char>, inc: Point) -> impl Iterator<Item = (usize, usize)> {
+    let (width, height) = (map.width() as i64, map.height() as i64);
+    let start = Point::new(0, if inc.y < 0 { height - 1 } else { 0 });
+    let steps = match inc.y {
+        _ if width == 0 => 0,
+        0 => 1,
+        y if y > 0 => (height + y - 1) / y,
+        y => (height - y - 1) / -y,
+    };
+
+    (0..steps).map(move |step| {
+        let pos = start + inc * step;
+        (pos.x.rem_euclid(width) as usize, pos.y as usize)
+    })
+}
+
+/// Trees (`#`) hit going across the map at slope `inc`, see [`path`].
+pub fn traverse(map: &Grid<char>, inc: Point) -> u32 {
+    path(map, inc)
+        .filter(|&(x, y)| map.get(x, y) == Some(&'#'))
+        .count() as u32
+}
+
+/// Trees hit on one slope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlopeHits {
+    pub slope: Point,
+    pub trees: u32,
+}
+
+/// Trees hit on each of `slopes`, fewest first, so the best slope is first
+/// and the worst last.
+pub fn slope_hits(map: &Grid<char>, slopes: impl IntoIterator<Item = Point>) -> Vec<SlopeHits> {
+    let mut hits: Vec<SlopeHits> = slopes
+        .into_iter()
+        .map(|slope| SlopeHits {
+            slope,
+            trees: traverse(map, slope),
+        })
+        .collect();
+    hits.sort_by_key(|hit| (hit.trees, hit.slope));
+    hits
+}
 
-    total
+/// Every slope with `x` and `y` in the given ranges, skipping the ones that
+/// never go up or down.
+pub fn slopes(xs: RangeInclusive<i64>, ys: RangeInclusive<i64>) -> impl Iterator<Item = Point> {
+    ys.filter(|&y| y != 0)
+        .flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
 }
 
-pub fn part1(map: Vec<Vec<char>>) -> u32 {
-    traverse(&map, Pos { x: 3, y: 1 })
+/// The map with the cells visited at slope `inc` marked, `O` where it was
+/// open and `X` where a tree was hit.
+pub fn render_path(map: &Grid<char>, inc: Point) -> Grid<char> {
+    let mut rendered = map.clone();
+    for (x, y) in path(map, inc) {
+        if let Some(cell) = rendered.get_mut(x, y) {
+            *cell = if *cell == '#' { 'X' } else { 'O' };
+        }
+    }
+    rendered
 }
 
-pub fn part2(map: Vec<Vec<char>>) -> u32 {
-    let rounds = vec![
-        traverse(&map, Pos { x: 1, y: 1 }),
-        traverse(&map, Pos { x: 3, y: 1 }),
-        traverse(&map, Pos { x: 5, y: 1 }),
-        traverse(&map, Pos { x: 7, y: 1 }),
-        traverse(&map, Pos { x: 1, y: 2 }),
-    ];
+/// Trees hit at slope right 3, down 1.
+pub fn part1(map: &Grid<char>) -> u32 {
+    traverse(map, Point::new(3, 1))
+}
 
-    rounds.into_iter().product()
+/// Product of the trees hit on the five puzzle slopes.
+pub fn part2(map: &Grid<char>) -> u32 {
+    PUZZLE_SLOPES
+        .iter()
+        .map(|&slope| traverse(map, slope))
+        .product()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util;
+    use crate::input;
 
     #[test]
     fn d3part1test() {
-        let data = util::grid_from_file("./data/day3.txt");
-        assert_eq!(part1(data), 247);
+        let data = Day03::parse(input::embedded(3).unwrap()).unwrap();
+        assert_eq!(part1(&data), 247);
     }
     #[test]
     fn d3part2test() {
-        let data = util::grid_from_file("./data/day3.txt");
-        assert_eq!(part2(data), 2983070376);
+        let data = Day03::parse(input::embedded(3).unwrap()).unwrap();
+        assert_eq!(part2(&data), 2983070376);
+    }
+
+    #[test]
+    fn d3slopestest() {
+        let data = Day03::parse(include_str!("../../data/examples/day3_example.txt")).unwrap();
+        let hits = slope_hits(&data, PUZZLE_SLOPES.iter().copied());
+
+        assert_eq!(
+            vec![2, 2, 3, 4, 7],
+            hits.iter().map(|hit| hit.trees).collect::<Vec<_>>()
+        );
+        assert_eq!(Point::new(3, 1), hits[4].slope);
+
+        // Going up from the bottom left hits the same cells as going down from there
+        let down: Vec<_> = path(&data, Point::new(0, 1)).collect();
+        let mut up: Vec<_> = path(&data, Point::new(0, -1)).collect();
+        up.reverse();
+        assert_eq!(down, up);
+        assert_eq!(
+            vec![(0, 0), (8, 1), (5, 2), (2, 3)],
+            path(&data, Point::new(-3, 1)).take(4).collect::<Vec<_>>()
+        );
+        assert_eq!(1, path(&data, Point::new(1, 0)).count());
+        assert_eq!(20, slopes(-2..=2, -2..=2).count());
+    }
+
+    #[test]
+    fn d3rendertest() {
+        let map = Grid::from_chars("..#\n#..\n.#.\n..#").unwrap();
+
+        assert_eq!(
+            "O.#\n#O.\n.#O\nO.#\n",
+            render_path(&map, Point::new(1, 1)).to_string()
+        );
+        assert_eq!(
+            "O.#\n#..\n.X.\n..#\n",
+            render_path(&map, Point::new(-2, 2)).to_string()
+        );
     }
 }
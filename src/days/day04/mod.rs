@@ -0,0 +1,375 @@
+//! Day 4: Passport Processing, validate blank-line separated passports.
+//!
+//! ```
+//! use adventofcode::days::day04;
+//!
+//! let passports = day04::parseport(
+//!     "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm\n\n\
+//!      iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\nhcl:#cfa07d byr:1929",
+//! )
+//! .unwrap();
+//! assert_eq!(passports.len(), 2);
+//! assert_eq!(passports[0].height.as_ref().unwrap().to_string(), "183cm");
+//! assert_eq!(day04::part1(&passports), 1);
+//! ```
+//!
+//! Passports are typed, see [`Passport`], and checked against a [`Schema`].
+
+use super::Solution;
+use crate::error::AocError;
+use crate::util;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    iter,
+    str::FromStr,
+    sync::OnceLock,
+};
+
+pub mod passport;
+pub mod schema;
+
+pub use passport::Passport;
+pub use schema::{Document, FieldError, Schema, Value};
+
+/// The raw fields of one record by key, before they're typed.
+pub type Fields = HashMap<String, String>;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = Vec<Passport>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Passport>, AocError> {
+        parseport(input)
+    }
+
+    fn part1(input: &Vec<Passport>) -> Option<u32> {
+        Some(part1(input))
+    }
+
+    fn part2(input: &Vec<Passport>) -> Option<u32> {
+        Some(part2(input))
+    }
+}
+
+/// One `key:value` field, `column` counting characters from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub line: usize,
+    pub column: usize,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+/// The fields of one line, separated by any whitespace. A value runs to the
+/// end of its field so it may contain `:` itself.
+pub fn tokenize(line_number: usize, line: &str) -> Result<Vec<Token<'_>>, AocError> {
+    let mut tokens = vec![];
+    let mut start = None;
+
+    // A space past the end closes the last field
+    for (i, c) in line.char_indices().chain(iter::once((line.len(), ' '))) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(from), true) => {
+                let text = &line[from..i];
+                let column = line[..from].chars().count() + 1;
+                let malformed = |reason| AocError::parse_at(line_number, column, text, reason);
+
+                let (key, value) = text
+                    .split_once(':')
+                    .ok_or_else(|| malformed("expected a key:value field"))?;
+                if key.is_empty() {
+                    return Err(malformed("field has no key"));
+                }
+                if value.is_empty() {
+                    return Err(malformed("field has no value"));
+                }
+
+                tokens.push(Token {
+                    line: line_number,
+                    column,
+                    key,
+                    value,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// What to do when a record has the same key twice.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    #[default]
+    Error,
+    FirstWins,
+    LastWins,
+}
+
+/// Read records separated by blank lines, a duplicate key is an error.
+pub fn parse_records(input: &str) -> Result<Vec<Fields>, AocError> {
+    parse_records_with(input, DuplicateKeys::Error)
+}
+
+/// Read records separated by blank lines, see [`tokenize`] for the fields.
+pub fn parse_records_with(input: &str, duplicates: DuplicateKeys) -> Result<Vec<Fields>, AocError> {
+    let mut records = vec![];
+
+    for record in util::records(input) {
+        let mut fields = HashMap::new();
+
+        for (line_number, row) in record.numbered_lines() {
+            for token in tokenize(line_number, row)? {
+                match (fields.entry(String::from(token.key)), duplicates) {
+                    (Entry::Vacant(entry), _) => {
+                        entry.insert(String::from(token.value));
+                    }
+                    (Entry::Occupied(_), DuplicateKeys::Error) => {
+                        return Err(AocError::parse_at(
+                            token.line,
+                            token.column,
+                            token.key,
+                            "duplicate key",
+                        ));
+                    }
+                    (Entry::Occupied(_), DuplicateKeys::FirstWins) => {}
+                    (Entry::Occupied(mut entry), DuplicateKeys::LastWins) => {
+                        entry.insert(String::from(token.value));
+                    }
+                }
+            }
+        }
+
+        records.push(fields);
+    }
+
+    Ok(records)
+}
+
+/// Read passports separated by blank lines, see [`parse_records`].
+pub fn parseport(parseports: &str) -> Result<Vec<Passport>, AocError> {
+    Ok(parse_records(parseports)?
+        .iter()
+        .map(Passport::from_fields)
+        .collect())
+}
+
+/// A single passport, the inverse of its `Display`.
+impl FromStr for Passport {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Passport, AocError> {
+        let mut records = parse_records(text)?;
+        if records.len() > 1 {
+            return Err(AocError::parse(1, text, "expected a single passport"));
+        }
+        Ok(records
+            .pop()
+            .map(|fields| Passport::from_fields(&fields))
+            .unwrap_or_default())
+    }
+}
+
+/// The day 4 rules, read and compiled the first time they're needed.
+fn passport_schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(Schema::passport)
+}
+
+/// What's wrong with a passport under the day 4 rules, empty when it's valid.
+pub fn validate(passport: &Passport) -> Vec<FieldError> {
+    passport_schema().validate(passport)
+}
+
+/// Passports with every required field present, `cid` is optional.
+pub fn part1(passports: &[Passport]) -> u32 {
+    let schema = passport_schema();
+    passports
+        .iter()
+        .filter(|passport| schema.has_required(*passport))
+        .count() as u32
+}
+
+/// Passports with every required field present and valid.
+pub fn part2(passports: &[Passport]) -> u32 {
+    let schema = passport_schema();
+    passports
+        .iter()
+        .filter(|passport| schema.is_valid(*passport))
+        .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::passport::{EyeColor, FieldValue, Height, HeightUnit};
+    use super::*;
+    use crate::input;
+
+    #[test]
+    fn d4part1test() {
+        let data = Day04::parse(input::embedded(4).unwrap()).unwrap();
+        assert_eq!(210, part1(&data));
+    }
+
+    #[test]
+    fn d4part2test() {
+        let data = Day04::parse(input::embedded(4).unwrap()).unwrap();
+        assert_eq!(131, part2(&data));
+    }
+
+    #[test]
+    fn d4validatetest() {
+        let passports = parseport(
+            "byr:1919 iyr:20x0 eyr:2030 hgt:190 hcl:123abc ecl:wat pid:01234567\n\n\
+             byr:2002 iyr:2010 eyr:2030 hgt:194cm hcl:#123abc ecl:brn pid:000000001 cid:1\n\n\
+             iyr:2019 hgt:59in",
+        )
+        .unwrap();
+        let errors = |index: usize| {
+            validate(&passports[index])
+                .iter()
+                .map(|err| err.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec![
+                "byr: 1919 is not between 1920 and 2002",
+                "iyr: 20x0 is not a number",
+                "hgt: 190 should end in one of cm, in",
+                "hcl: 123abc is not like ^#[0-9a-f]{6}$",
+                "ecl: wat is not one of amb, blu, brn, gry, grn, hzl, oth",
+                "pid: 01234567 is not 9 digits",
+            ],
+            errors(0)
+        );
+        assert_eq!(vec!["hgt: 194cm is not between 150 and 193"], errors(1));
+        assert_eq!(
+            vec!["byr", "eyr", "hcl", "ecl", "pid"],
+            validate(&passports[2])
+                .iter()
+                .map(|err| err.field())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            FieldError::Missing {
+                field: String::from("byr")
+            },
+            validate(&passports[2])[0]
+        );
+
+        // Typed fields have to fail the same way as their text
+        let input = input::embedded(4).unwrap();
+        let schema = Schema::passport();
+        for (passport, fields) in parseport(input)
+            .unwrap()
+            .iter()
+            .zip(parse_records(input).unwrap())
+        {
+            assert_eq!(schema.validate(&fields), validate(passport));
+        }
+    }
+
+    #[test]
+    fn d4tokenizetest() {
+        let tokens = tokenize(3, " a:1\tbb:x:y   c:é").unwrap();
+        assert_eq!(
+            vec![(2, "a", "1"), (6, "bb", "x:y"), (15, "c", "é")],
+            tokens
+                .iter()
+                .map(|token| (token.column, token.key, token.value))
+                .collect::<Vec<_>>()
+        );
+
+        let err = |input| parse_records(input).unwrap_err().to_string();
+        assert_eq!(
+            "line 2, column 5: expected a key:value field: \"oops\"",
+            err("a:1\nb:2 oops")
+        );
+        assert_eq!("line 1, column 1: field has no key: \":1\"", err(":1"));
+        assert_eq!(
+            "line 1, column 5: field has no value: \"b:\"",
+            err("a:1 b:")
+        );
+        assert_eq!(
+            "line 4, column 5: duplicate key: \"a\"",
+            err("a:1\n\na:1\nb:2 a:3")
+        );
+
+        let value = |duplicates| parse_records_with("a:1 a:2", duplicates).unwrap()[0]["a"].clone();
+        assert_eq!("1", value(DuplicateKeys::FirstWins));
+        assert_eq!("2", value(DuplicateKeys::LastWins));
+    }
+
+    #[test]
+    fn d4typedtest() {
+        let passport: Passport = "hgt:59in byr:abc zzz:1 hcl:#0A0b0c ecl:hzl pid:000000001"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            Some(&Height {
+                value: 59,
+                unit: HeightUnit::In
+            }),
+            passport.height.as_ref().and_then(FieldValue::typed)
+        );
+        assert_eq!(
+            Some(FieldValue::Raw(String::from("abc"))),
+            passport.birth_year
+        );
+        assert_eq!(
+            Some(FieldValue::Raw(String::from("#0A0b0c"))),
+            passport.hair_color
+        );
+        assert_eq!(Some(FieldValue::Typed(EyeColor::Hzl)), passport.eye_color);
+        assert_eq!(Some("1"), passport.unknown.get("zzz").map(|v| &v[..]));
+        assert_eq!(
+            "byr:abc hgt:59in hcl:#0A0b0c ecl:hzl pid:000000001 zzz:1",
+            passport.to_string()
+        );
+        assert!("a:1\n\nb:2".parse::<Passport>().is_err());
+    }
+
+    #[test]
+    fn d4roundtriptest() {
+        let passports = Day04::parse(input::embedded(4).unwrap()).unwrap();
+
+        for passport in passports {
+            assert_eq!(passport, passport.to_string().parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn d4schematest() {
+        let schema = Schema::from_toml(
+            "[[field]]\nname = \"id\"\nrule = { kind = \"regex\", pattern = \"^[A-Z]{2}[0-9]+$\" }\n\n\
+             [[field]]\nname = \"age\"\nrequired = false\nrule = { kind = \"units\", ranges = [{ unit = \"y\", min = 18, max = 99 }] }",
+        )
+        .unwrap();
+        let documents =
+            parse_records("id:AB12 age:30y\n\nid:AB12 age:30\n\nage:30y\n\nid:AB").unwrap();
+
+        assert_eq!(
+            vec![true, false, false, false],
+            documents
+                .iter()
+                .map(|document| schema.is_valid(document))
+                .collect::<Vec<_>>()
+        );
+        assert!(schema.has_required(&documents[1]));
+        assert!(Schema::from_toml(
+            "[[field]]\nname = \"x\"\nrule = { kind = \"regex\", pattern = \"(\" }"
+        )
+        .is_err());
+        assert!(
+            Schema::from_toml("[[field]]\nname = \"x\"\nrule = { kind = \"colour\" }").is_err()
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use super::{
+    schema::{Document, Value},
+    Fields,
+};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// A value that can't be read as the type its field wants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadValue(pub String);
+
+impl fmt::Display for BadValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bad value {:?}", self.0)
+    }
+}
+
+/// A field that was there: typed when its value reads as `T` and writes back
+/// exactly the same, otherwise kept as it was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue<T> {
+    Typed(T),
+    Raw(String),
+}
+
+impl<T: FromStr + fmt::Display> FieldValue<T> {
+    pub fn read(value: &str) -> FieldValue<T> {
+        match value.parse::<T>() {
+            Ok(typed) if typed.to_string() == value => FieldValue::Typed(typed),
+            _ => FieldValue::Raw(String::from(value)),
+        }
+    }
+
+    pub fn typed(&self) -> Option<&T> {
+        match self {
+            FieldValue::Typed(typed) => Some(typed),
+            FieldValue::Raw(_) => None,
+        }
+    }
+}
+
+impl<T> FieldValue<T> {
+    fn value<'a>(&'a self, typed: impl FnOnce(&'a T) -> Value<'a>) -> Value<'a> {
+        match self {
+            FieldValue::Typed(value) => typed(value),
+            FieldValue::Raw(raw) => Value::Text(raw),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for FieldValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Typed(typed) => write!(f, "{}", typed),
+            FieldValue::Raw(raw) => write!(f, "{}", raw),
+        }
+    }
+}
+
+macro_rules! year {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        pub struct $name(pub u32);
+
+        impl FromStr for $name {
+            type Err = BadValue;
+
+            fn from_str(value: &str) -> Result<$name, BadValue> {
+                value
+                    .parse()
+                    .map($name)
+                    .map_err(|_| BadValue(String::from(value)))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+year!(
+    /// `byr`
+    BirthYear
+);
+year!(
+    /// `iyr`
+    IssueYear
+);
+year!(
+    /// `eyr`
+    ExpirationYear
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeightUnit {
+    Cm,
+    In,
+}
+
+/// `hgt`, a number and its unit like `183cm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Height {
+    pub value: u32,
+    pub unit: HeightUnit,
+}
+
+impl FromStr for Height {
+    type Err = BadValue;
+
+    fn from_str(value: &str) -> Result<Height, BadValue> {
+        let bad = || BadValue(String::from(value));
+        let (number, unit) = match value.strip_suffix("cm") {
+            Some(number) => (number, HeightUnit::Cm),
+            None => (value.strip_suffix("in").ok_or_else(bad)?, HeightUnit::In),
+        };
+
+        Ok(Height {
+            value: number.parse().map_err(|_| bad())?,
+            unit,
+        })
+    }
+}
+
+impl HeightUnit {
+    pub fn suffix(self) -> &'static str {
+        match self {
+            HeightUnit::Cm => "cm",
+            HeightUnit::In => "in",
+        }
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.suffix())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl FromStr for Rgb {
+    type Err = BadValue;
+
+    fn from_str(value: &str) -> Result<Rgb, BadValue> {
+        let bad = || BadValue(String::from(value));
+        let hex = value.strip_prefix('#').ok_or_else(bad)?;
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(bad());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| bad());
+
+        Ok(Rgb {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        })
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// `hcl`, written `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HairColor(pub Rgb);
+
+impl FromStr for HairColor {
+    type Err = BadValue;
+
+    fn from_str(value: &str) -> Result<HairColor, BadValue> {
+        value.parse().map(HairColor)
+    }
+}
+
+impl fmt::Display for HairColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// `ecl`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EyeColor {
+    Amb,
+    Blu,
+    Brn,
+    Gry,
+    Grn,
+    Hzl,
+    Oth,
+}
+
+impl EyeColor {
+    pub const ALL: [EyeColor; 7] = [
+        EyeColor::Amb,
+        EyeColor::Blu,
+        EyeColor::Brn,
+        EyeColor::Gry,
+        EyeColor::Grn,
+        EyeColor::Hzl,
+        EyeColor::Oth,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            EyeColor::Amb => "amb",
+            EyeColor::Blu => "blu",
+            EyeColor::Brn => "brn",
+            EyeColor::Gry => "gry",
+            EyeColor::Grn => "grn",
+            EyeColor::Hzl => "hzl",
+            EyeColor::Oth => "oth",
+        }
+    }
+}
+
+impl FromStr for EyeColor {
+    type Err = BadValue;
+
+    fn from_str(value: &str) -> Result<EyeColor, BadValue> {
+        EyeColor::ALL
+            .iter()
+            .copied()
+            .find(|colour| colour.code() == value)
+            .ok_or_else(|| BadValue(String::from(value)))
+    }
+}
+
+impl fmt::Display for EyeColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// `pid`, digits kept as written so leading zeros survive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassportId(pub String);
+
+impl FromStr for PassportId {
+    type Err = BadValue;
+
+    fn from_str(value: &str) -> Result<PassportId, BadValue> {
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(BadValue(String::from(value)));
+        }
+        Ok(PassportId(String::from(value)))
+    }
+}
+
+impl fmt::Display for PassportId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A passport with each known field typed, `None` when it's missing.
+/// Writes back as `key:value` fields on one line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Passport {
+    pub birth_year: Option<FieldValue<BirthYear>>,
+    pub issue_year: Option<FieldValue<IssueYear>>,
+    pub expiration_year: Option<FieldValue<ExpirationYear>>,
+    pub height: Option<FieldValue<Height>>,
+    pub hair_color: Option<FieldValue<HairColor>>,
+    pub eye_color: Option<FieldValue<EyeColor>>,
+    pub passport_id: Option<FieldValue<PassportId>>,
+    /// `cid`, which nothing checks.
+    pub country_id: Option<String>,
+    /// Fields passports don't have, by key.
+    pub unknown: BTreeMap<String, String>,
+}
+
+impl Passport {
+    pub fn from_fields(fields: &Fields) -> Passport {
+        let mut passport = Passport::default();
+
+        for (key, value) in fields {
+            match &key[..] {
+                "byr" => passport.birth_year = Some(FieldValue::read(value)),
+                "iyr" => passport.issue_year = Some(FieldValue::read(value)),
+                "eyr" => passport.expiration_year = Some(FieldValue::read(value)),
+                "hgt" => passport.height = Some(FieldValue::read(value)),
+                "hcl" => passport.hair_color = Some(FieldValue::read(value)),
+                "ecl" => passport.eye_color = Some(FieldValue::read(value)),
+                "pid" => passport.passport_id = Some(FieldValue::read(value)),
+                "cid" => passport.country_id = Some(value.clone()),
+                _ => {
+                    passport.unknown.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        passport
+    }
+
+    /// Every field as `(key, value)`, known ones first in puzzle order.
+    pub fn entries(&self) -> Vec<(&str, String)> {
+        let known = vec![
+            ("byr", self.birth_year.as_ref().map(|v| v.to_string())),
+            ("iyr", self.issue_year.as_ref().map(|v| v.to_string())),
+            ("eyr", self.expiration_year.as_ref().map(|v| v.to_string())),
+            ("hgt", self.height.as_ref().map(|v| v.to_string())),
+            ("hcl", self.hair_color.as_ref().map(|v| v.to_string())),
+            ("ecl", self.eye_color.as_ref().map(|v| v.to_string())),
+            ("pid", self.passport_id.as_ref().map(|v| v.to_string())),
+            ("cid", self.country_id.clone()),
+        ];
+
+        known
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value?)))
+            .chain(
+                self.unknown
+                    .iter()
+                    .map(|(key, value)| (&key[..], value.clone())),
+            )
+            .collect()
+    }
+}
+
+/// Typed fields are checked as they are, only raw ones are read again.
+impl Document for Passport {
+    fn value(&self, key: &str) -> Option<Value<'_>> {
+        let year = |year: u32| Value::Number(year);
+
+        match key {
+            "byr" => self.birth_year.as_ref().map(|v| v.value(|y| year(y.0))),
+            "iyr" => self.issue_year.as_ref().map(|v| v.value(|y| year(y.0))),
+            "eyr" => self
+                .expiration_year
+                .as_ref()
+                .map(|v| v.value(|y| year(y.0))),
+            "hgt" => self.height.as_ref().map(|v| {
+                v.value(|height| Value::Measure {
+                    value: height.value,
+                    unit: height.unit.suffix(),
+                })
+            }),
+            "hcl" => self
+                .hair_color
+                .as_ref()
+                .map(|v| v.value(|colour| Value::Other(colour))),
+            "ecl" => self
+                .eye_color
+                .as_ref()
+                .map(|v| v.value(|colour| Value::Text(colour.code()))),
+            "pid" => self
+                .passport_id
+                .as_ref()
+                .map(|v| v.value(|id| Value::Text(&id.0))),
+            "cid" => self.country_id.as_deref().map(Value::Text),
+            _ => self.unknown.get(key).map(|value| Value::Text(value)),
+        }
+    }
+}
+
+impl fmt::Display for Passport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.entries().iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}:{}", key, value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+//! Rules for documents made of `key:value` fields, read from TOML so other
+//! document types can be checked the same way as passports:
+//!
+//! ```toml
+//! [[field]]
+//! name = "byr"
+//! rule = { kind = "year", min = 1920, max = 2002 }
+//!
+//! [[field]]
+//! name = "cid"
+//! required = false
+//! ```
+
+use crate::{error::AocError, util};
+use regex::Regex;
+use serde::Deserialize;
+use std::{collections::HashMap, convert::TryFrom, fmt, path::Path};
+
+/// The fields a document should have, in the order they're checked.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Schema {
+    #[serde(rename = "field", default)]
+    pub fields: Vec<FieldSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldSpec {
+    pub name: String,
+    #[serde(default = "required_default")]
+    pub required: bool,
+    /// What the value has to look like, anything goes without one.
+    pub rule: Option<Rule>,
+}
+
+fn required_default() -> bool {
+    true
+}
+
+/// How a field's value is checked.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Rule {
+    /// Four digits between `min` and `max`.
+    Year { min: u32, max: u32 },
+    /// A number followed by one of the units, in that unit's range.
+    Units { ranges: Vec<UnitRange> },
+    /// Matches the pattern, anchor it to match the whole value.
+    Regex { pattern: Pattern },
+    /// One of `values` exactly.
+    OneOf { values: Vec<String> },
+    /// Exactly `len` ASCII digits, leading zeros included.
+    Digits { len: usize },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UnitRange {
+    pub unit: String,
+    pub min: u32,
+    pub max: u32,
+}
+
+/// A regex compiled once when the schema is read.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Pattern(pub Regex);
+
+impl TryFrom<String> for Pattern {
+    type Error = regex::Error;
+
+    fn try_from(pattern: String) -> Result<Pattern, regex::Error> {
+        Regex::new(&pattern).map(Pattern)
+    }
+}
+
+/// Why a field of a document isn't valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    Missing {
+        field: String,
+    },
+    OutOfRange {
+        field: String,
+        value: String,
+        min: u32,
+        max: u32,
+    },
+    /// None of the allowed units, or no unit at all.
+    BadUnit {
+        field: String,
+        value: String,
+        units: Vec<String>,
+    },
+    BadFormat {
+        field: String,
+        value: String,
+        expected: String,
+    },
+    NotANumber {
+        field: String,
+        value: String,
+    },
+}
+
+impl FieldError {
+    pub fn field(&self) -> &str {
+        match self {
+            FieldError::Missing { field }
+            | FieldError::OutOfRange { field, .. }
+            | FieldError::BadUnit { field, .. }
+            | FieldError::BadFormat { field, .. }
+            | FieldError::NotANumber { field, .. } => field,
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::Missing { field } => write!(f, "{}: missing", field),
+            FieldError::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(f, "{}: {} is not between {} and {}", field, value, min, max),
+            FieldError::BadUnit {
+                field,
+                value,
+                units,
+            } => write!(
+                f,
+                "{}: {} should end in one of {}",
+                field,
+                value,
+                units.join(", ")
+            ),
+            FieldError::BadFormat {
+                field,
+                value,
+                expected,
+            } => write!(f, "{}: {} is not {}", field, value, expected),
+            FieldError::NotANumber { field, value } => {
+                write!(f, "{}: {} is not a number", field, value)
+            }
+        }
+    }
+}
+
+/// A field's value, already read as a number, or a number and unit, when
+/// the document has typed it, so rules don't parse it again.
+#[derive(Clone, Copy)]
+pub enum Value<'a> {
+    Text(&'a str),
+    Number(u32),
+    Measure {
+        value: u32,
+        unit: &'a str,
+    },
+    /// Anything else, written out when a rule needs its text.
+    Other(&'a dyn fmt::Display),
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Text(text) => write!(f, "{}", text),
+            Value::Number(number) => write!(f, "{}", number),
+            Value::Measure { value, unit } => write!(f, "{}{}", value, unit),
+            Value::Other(other) => write!(f, "{}", other),
+        }
+    }
+}
+
+/// Something made of `key:value` fields a [`Schema`] can check.
+pub trait Document {
+    fn value(&self, key: &str) -> Option<Value<'_>>;
+}
+
+impl Document for HashMap<String, String> {
+    fn value(&self, key: &str) -> Option<Value<'_>> {
+        self.get(key).map(|value| Value::Text(value))
+    }
+}
+
+impl Rule {
+    pub fn matches(&self, value: &str) -> bool {
+        self.check("", value).is_ok()
+    }
+
+    /// Check `value` of the field called `field`.
+    pub fn check(&self, field: &str, value: &str) -> Result<(), FieldError> {
+        self.check_value(field, Value::Text(value))
+    }
+
+    /// Check a value that may already be typed, its text is only written out
+    /// when a rule needs it or the value is wrong.
+    pub fn check_value(&self, field: &str, value: Value<'_>) -> Result<(), FieldError> {
+        let bad_format = |expected: String| FieldError::BadFormat {
+            field: String::from(field),
+            value: value.to_string(),
+            expected,
+        };
+        let not_a_number = || FieldError::NotANumber {
+            field: String::from(field),
+            value: value.to_string(),
+        };
+        let number = |digits: &str| digits.parse::<u32>().map_err(|_| not_a_number());
+        let in_range = |number: u32, min: u32, max: u32| {
+            if (min..=max).contains(&number) {
+                Ok(())
+            } else {
+                Err(FieldError::OutOfRange {
+                    field: String::from(field),
+                    value: value.to_string(),
+                    min,
+                    max,
+                })
+            }
+        };
+        let bad_unit = |ranges: &[UnitRange]| FieldError::BadUnit {
+            field: String::from(field),
+            value: value.to_string(),
+            units: ranges.iter().map(|range| range.unit.clone()).collect(),
+        };
+
+        match (self, value) {
+            // Typed numbers are written without leading zeros
+            (Rule::Year { min, max }, Value::Number(year)) => {
+                if !(1000..=9999).contains(&year) {
+                    return Err(bad_format(String::from("a four digit year")));
+                }
+                in_range(year, *min, *max)
+            }
+            (Rule::Year { min, max }, Value::Text(text)) => {
+                let year = number(text)?;
+                if text.len() != 4 {
+                    return Err(bad_format(String::from("a four digit year")));
+                }
+                in_range(year, *min, *max)
+            }
+            (Rule::Units { ranges }, Value::Measure { value, unit }) => {
+                let range = ranges
+                    .iter()
+                    .find(|range| range.unit == unit)
+                    .ok_or_else(|| bad_unit(ranges))?;
+                in_range(value, range.min, range.max)
+            }
+            (Rule::Units { ranges }, Value::Text(text)) => {
+                let (range, digits) = ranges
+                    .iter()
+                    .find_map(|range| Some((range, text.strip_suffix(&range.unit[..])?)))
+                    .ok_or_else(|| bad_unit(ranges))?;
+                in_range(number(digits)?, range.min, range.max)
+            }
+            (Rule::Regex { pattern }, Value::Text(text)) => {
+                if !pattern.0.is_match(text) {
+                    return Err(bad_format(format!("like {}", pattern.0)));
+                }
+                Ok(())
+            }
+            (Rule::OneOf { values }, Value::Text(text)) => {
+                if !values.iter().any(|v| v == text) {
+                    return Err(bad_format(format!("one of {}", values.join(", "))));
+                }
+                Ok(())
+            }
+            (Rule::Digits { len }, Value::Text(text)) => {
+                if !text.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(not_a_number());
+                }
+                if text.len() != *len {
+                    return Err(bad_format(format!("{} digits", len)));
+                }
+                Ok(())
+            }
+            // A typed value the rule only knows how to read as text
+            (_, value) => self.check(field, &value.to_string()),
+        }
+    }
+}
+
+impl Schema {
+    /// The day 4 passport fields, from `data/passport_schema.toml`.
+    pub fn passport() -> Schema {
+        Schema::from_toml(include_str!("../../../data/passport_schema.toml"))
+            .expect("checked-in passport schema should be valid")
+    }
+
+    pub fn from_toml(text: &str) -> Result<Schema, AocError> {
+        toml::from_str(text).map_err(|err| AocError::Config {
+            path: None,
+            reason: err.to_string(),
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Schema, AocError> {
+        let path = path.as_ref();
+        Schema::from_toml(&util::read_file(path)?).map_err(|err| err.with_path(path))
+    }
+
+    /// Every required field is there, whatever its value.
+    pub fn has_required<D: Document + ?Sized>(&self, document: &D) -> bool {
+        self.fields
+            .iter()
+            .all(|field| !field.required || document.value(&field.name).is_some())
+    }
+
+    /// Every required field that's missing and every field that doesn't follow
+    /// its rule, in schema order. Fields the schema doesn't know are ignored.
+    pub fn validate<D: Document + ?Sized>(&self, document: &D) -> Vec<FieldError> {
+        self.fields
+            .iter()
+            .filter_map(|field| match (document.value(&field.name), &field.rule) {
+                (None, _) if field.required => Some(FieldError::Missing {
+                    field: field.name.clone(),
+                }),
+                (Some(value), Some(rule)) => rule.check_value(&field.name, value).err(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Every required field is there and every field that is there follows its rule.
+    pub fn is_valid<D: Document + ?Sized>(&self, document: &D) -> bool {
+        self.validate(document).is_empty()
+    }
+}
@@ -1,67 +1,387 @@
-pub fn parse_instructions(characters: &[char], target: &char) -> String {
-    characters
-        .into_iter()
-        .map(|i| {
-            if i == target {
-                String::from("1")
-            } else {
-                String::from("0")
-            }
-        })
-        .collect::<Vec<String>>()
-        .join("")
+//! Day 5: Binary Boarding, decode boarding passes into seat ids.
+//!
+//! ```
+//! use adventofcode::days::day05;
+//!
+//! let seats = day05::get_seat_ids("FBFBBFFRLR\nBFFFBBFRRR").unwrap();
+//! assert_eq!(seats, vec![357, 567]);
+//! assert_eq!(day05::part1(&seats), Some(567));
+//! ```
+//!
+//! Other planes decode the same way with their own [`SeatLayout`].
+
+use super::Solution;
+use crate::error::AocError;
+use std::{fmt, ops::Range};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, AocError> {
+        get_seat_ids(input)
+    }
+
+    fn part1(input: &Vec<usize>) -> Option<usize> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<usize>) -> Option<usize> {
+        part2(input)
+    }
+}
+
+/// How boarding passes encode a seat: the row as `row_bits` characters then
+/// the column as `col_bits`, each one picking the upper or lower half of what's
+/// left. Built with [`SeatLayout::new`] so every seat id fits in a `usize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeatLayout {
+    row_bits: u32,
+    col_bits: u32,
+    row_upper: char,
+    row_lower: char,
+    col_upper: char,
+    col_lower: char,
+}
+
+/// A decoded pass, `row` and `col` counting from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Seat {
+    pub row: usize,
+    pub col: usize,
 }
 
-pub fn get_seat_ids(data: Vec<Vec<char>>) -> Vec<usize> {
-    let mut seats = vec![];
+impl SeatLayout {
+    /// The puzzle's plane, 128 rows of 8 seats, `F`/`B` then `L`/`R`.
+    pub const PUZZLE: SeatLayout = SeatLayout {
+        row_bits: 7,
+        col_bits: 3,
+        row_upper: 'B',
+        row_lower: 'F',
+        col_upper: 'R',
+        col_lower: 'L',
+    };
+
+    /// Fails when there are too many seats to number or the two characters
+    /// for the row or for the column are the same.
+    pub fn new(
+        row_bits: u32,
+        col_bits: u32,
+        row_upper: char,
+        row_lower: char,
+        col_upper: char,
+        col_lower: char,
+    ) -> Result<SeatLayout, AocError> {
+        let invalid = |reason| AocError::Config { path: None, reason };
+
+        if row_bits
+            .checked_add(col_bits)
+            .is_none_or(|bits| bits >= usize::BITS)
+        {
+            return Err(invalid(format!(
+                "{} row and {} column characters is more seats than fit in a usize",
+                row_bits, col_bits
+            )));
+        }
+        if row_upper == row_lower {
+            return Err(invalid(format!("row halves are both {:?}", row_upper)));
+        }
+        if col_upper == col_lower {
+            return Err(invalid(format!("column halves are both {:?}", col_upper)));
+        }
+
+        Ok(SeatLayout {
+            row_bits,
+            col_bits,
+            row_upper,
+            row_lower,
+            col_upper,
+            col_lower,
+        })
+    }
+
+    pub fn row_bits(&self) -> u32 {
+        self.row_bits
+    }
+
+    pub fn col_bits(&self) -> u32 {
+        self.col_bits
+    }
+
+    pub fn rows(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn cols(&self) -> usize {
+        1 << self.col_bits
+    }
+
+    /// Seats numbered row by row from 0.
+    pub fn seat_id(&self, seat: Seat) -> usize {
+        seat.row * self.cols() + seat.col
+    }
+
+    pub fn seat(&self, seat_id: usize) -> Seat {
+        Seat {
+            row: seat_id / self.cols(),
+            col: seat_id % self.cols(),
+        }
+    }
 
-    for pass in &data {
-        let row = &pass.get(0..7).unwrap();
-        let col = &pass.get(7..10).unwrap();
+    /// The seat for one pass, failing when it doesn't fit the layout.
+    pub fn decode(&self, line_number: usize, pass: &str) -> Result<Seat, AocError> {
+        let length = (self.row_bits + self.col_bits) as usize;
+        if pass.chars().count() != length {
+            return Err(AocError::parse(
+                line_number,
+                pass,
+                format!("expected {} character boarding pass", length),
+            ));
+        }
 
-        let row_instructions = &parse_instructions(&row, &'B'); // Position increases with B
-        let col_instructions = &parse_instructions(&col, &'R'); // Position increases with R
+        let mut seat = Seat { row: 0, col: 0 };
+        for (i, c) in pass.chars().enumerate() {
+            let (half, upper, lower) = if i < self.row_bits as usize {
+                (&mut seat.row, self.row_upper, self.row_lower)
+            } else {
+                (&mut seat.col, self.col_upper, self.col_lower)
+            };
 
-        let row_location = usize::from_str_radix(row_instructions, 2).unwrap();
-        let col_location = usize::from_str_radix(col_instructions, 2).unwrap();
+            *half <<= 1;
+            if c == upper {
+                *half |= 1;
+            } else if c != lower {
+                return Err(AocError::parse_at(
+                    line_number,
+                    i + 1,
+                    &c.to_string(),
+                    format!("expected {} or {}", lower, upper),
+                ));
+            }
+        }
 
-        seats.push(row_location * 8 + col_location);
+        Ok(seat)
     }
+}
 
-    seats
+/// Seat id (`row * 8 + column`) of every pass, one pass per line.
+pub fn get_seat_ids(passes: &str) -> Result<Vec<usize>, AocError> {
+    get_seat_ids_with(passes, &SeatLayout::PUZZLE)
 }
 
-pub fn part1(data: Vec<Vec<char>>) -> usize {
-    let seats = get_seat_ids(data);
-    seats.into_iter().max().unwrap()
+/// Seat id of every pass on a plane with this `layout`.
+pub fn get_seat_ids_with(passes: &str, layout: &SeatLayout) -> Result<Vec<usize>, AocError> {
+    passes
+        .lines()
+        .enumerate()
+        .map(|(index, pass)| Ok(layout.seat_id(layout.decode(index + 1, pass)?)))
+        .collect()
 }
 
-pub fn part2(data: Vec<Vec<char>>) -> Option<usize> {
-    let seats = get_seat_ids(data);
+/// Which seats of a plane are taken, one bit per seat id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatMap {
+    layout: SeatLayout,
+    occupied: Vec<u64>,
+}
+
+impl SeatMap {
+    /// Most seats a map will hold, 2 MiB of bits.
+    pub const MAX_SEATS: usize = 1 << 24;
+
+    /// The plane with `seat_ids` taken, ids past the last seat are ignored.
+    /// Fails when the layout has more than [`MAX_SEATS`](SeatMap::MAX_SEATS).
+    pub fn new(layout: SeatLayout, seat_ids: &[usize]) -> Result<SeatMap, AocError> {
+        let seats = layout.rows() * layout.cols();
+        if seats > SeatMap::MAX_SEATS {
+            return Err(AocError::Config {
+                path: None,
+                reason: format!(
+                    "{} seats is more than a seat map holds ({})",
+                    seats,
+                    SeatMap::MAX_SEATS
+                ),
+            });
+        }
+
+        let mut occupied = vec![0; seats.div_ceil(64)];
+        for &id in seat_ids.iter().filter(|&&id| id < seats) {
+            occupied[id / 64] |= 1 << (id % 64);
+        }
+
+        Ok(SeatMap { layout, occupied })
+    }
+
+    pub fn layout(&self) -> &SeatLayout {
+        &self.layout
+    }
+
+    fn seats(&self) -> usize {
+        self.layout.rows() * self.layout.cols()
+    }
+
+    pub fn is_occupied(&self, seat_id: usize) -> bool {
+        seat_id < self.seats() && self.occupied[seat_id / 64] & (1 << (seat_id % 64)) != 0
+    }
+
+    // Whole words of taken seats are skipped without looking at their bits
+    fn empty_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        let seats = self.seats();
+        self.occupied
+            .iter()
+            .enumerate()
+            .filter(|&(_, &word)| word != u64::MAX)
+            .flat_map(|(index, &word)| {
+                (0..64)
+                    .filter(move |bit| word & (1 << bit) == 0)
+                    .map(move |bit| index * 64 + bit)
+            })
+            .take_while(move |&id| id < seats)
+    }
+
+    pub fn empty_seats(&self) -> Vec<usize> {
+        self.empty_ids().collect()
+    }
+
+    /// Empty seats whose ids either side are both taken, the first and last
+    /// seats never are.
+    pub fn empty_between_occupied(&self) -> Vec<usize> {
+        self.empty_ids()
+            .filter(|&id| {
+                id.checked_sub(1)
+                    .is_some_and(|before| self.is_occupied(before))
+                    && self.is_occupied(id + 1)
+            })
+            .collect()
+    }
+
+    /// Runs of consecutive empty seat ids, in order.
+    pub fn empty_blocks(&self) -> Vec<Range<usize>> {
+        let mut blocks: Vec<Range<usize>> = vec![];
+        for id in self.empty_ids() {
+            match blocks.last_mut() {
+                Some(block) if block.end == id => block.end += 1,
+                _ => blocks.push(id..id + 1),
+            }
+        }
+        blocks
+    }
+}
 
-    for seat in 0..seats.len() {
-        if !seats.contains(&seat) && seats.contains(&(seat + 1)) && seats.contains(&(seat - 1)) {
-            return Some(seat);
+/// The cabin a row per line, `#` taken and `.` empty, with row numbers down
+/// the side.
+impl fmt::Display for SeatMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = (self.layout.rows() - 1).to_string().len();
+        for row in 0..self.layout.rows() {
+            let seats: String = (0..self.layout.cols())
+                .map(|col| self.layout.seat_id(Seat { row, col }))
+                .map(|id| if self.is_occupied(id) { '#' } else { '.' })
+                .collect();
+            writeln!(f, "{:>width$} {}", row, seats, width = width)?;
         }
+        Ok(())
     }
+}
+
+/// Highest seat id.
+pub fn part1(seats: &[usize]) -> Option<usize> {
+    seats.iter().max().copied()
+}
 
-    None
+/// The missing seat id with both neighbours taken.
+pub fn part2(seats: &[usize]) -> Option<usize> {
+    SeatMap::new(SeatLayout::PUZZLE, seats)
+        .ok()?
+        .empty_between_occupied()
+        .first()
+        .copied()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util;
+    use crate::input;
 
     #[test]
     fn d5part1test() {
-        let data = util::grid_from_file("./data/day5.txt");
-        assert_eq!(816, part1(data));
+        let data = Day05::parse(input::embedded(5).unwrap()).unwrap();
+        assert_eq!(816, part1(&data).unwrap());
     }
 
     #[test]
     fn d5part2test() {
-        let data = util::grid_from_file("./data/day5.txt");
-        assert_eq!(539, part2(data).unwrap());
+        let data = Day05::parse(input::embedded(5).unwrap()).unwrap();
+        assert_eq!(539, part2(&data).unwrap());
+    }
+
+    #[test]
+    fn d5seatmaptest() {
+        let layout = SeatLayout::new(2, 2, 'B', 'F', 'R', 'L').unwrap();
+        let map = SeatMap::new(layout, &[1, 2, 4, 6, 7, 8, 20]).unwrap();
+
+        assert_eq!(vec![0, 3, 5, 9, 10, 11, 12, 13, 14, 15], map.empty_seats());
+        assert_eq!(vec![3, 5], map.empty_between_occupied());
+        assert_eq!(vec![0..1, 3..4, 5..6, 9..16], map.empty_blocks());
+        assert_eq!("0 .##.\n1 #.##\n2 #...\n3 ....\n", map.to_string());
+        assert_eq!(None, part2(&[0]));
+
+        let huge = SeatLayout::new(30, 30, 'B', 'F', 'R', 'L').unwrap();
+        assert_eq!(
+            "1152921504606846976 seats is more than a seat map holds (16777216)",
+            SeatMap::new(huge, &[0]).unwrap_err().to_string()
+        );
+        let large = SeatLayout::new(21, 3, 'B', 'F', 'R', 'L').unwrap();
+        let map = SeatMap::new(large, &[0, 5, 70, SeatMap::MAX_SEATS - 1]).unwrap();
+        assert_eq!(
+            vec![1..5, 6..70, 71..SeatMap::MAX_SEATS - 1],
+            map.empty_blocks()
+        );
+    }
+
+    #[test]
+    fn d5layouttest() {
+        let layout = SeatLayout::new(2, 2, 'D', 'U', '>', '<').unwrap();
+        let seat = layout.decode(1, "DU<>").unwrap();
+
+        assert_eq!(Seat { row: 2, col: 1 }, seat);
+        assert_eq!(9, layout.seat_id(seat));
+        assert_eq!(seat, layout.seat(9));
+        assert_eq!(
+            vec![0, 15],
+            get_seat_ids_with("UU<<\nDD>>", &layout).unwrap()
+        );
+        assert_eq!(
+            "line 2, column 3: expected < or >: \"R\"",
+            get_seat_ids_with("UU<<\nDDR>", &layout)
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "line 1: expected 10 character boarding pass: \"DU<>\"",
+            get_seat_ids("DU<>").unwrap_err().to_string()
+        );
+
+        let invalid = |layout: Result<SeatLayout, AocError>| layout.unwrap_err().to_string();
+        assert_eq!(
+            "40 row and 24 column characters is more seats than fit in a usize",
+            invalid(SeatLayout::new(40, 24, 'B', 'F', 'R', 'L'))
+        );
+        assert!(SeatLayout::new(u32::MAX, 1, 'B', 'F', 'R', 'L').is_err());
+        assert_eq!(
+            "row halves are both 'B'",
+            invalid(SeatLayout::new(7, 3, 'B', 'B', 'R', 'L'))
+        );
+        assert_eq!(
+            "column halves are both 'L'",
+            invalid(SeatLayout::new(7, 3, 'B', 'F', 'L', 'L'))
+        );
+        assert_eq!(
+            Ok(SeatLayout::PUZZLE),
+            SeatLayout::new(7, 3, 'B', 'F', 'R', 'L').map_err(|_| ())
+        );
     }
 }
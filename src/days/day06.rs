@@ -1,81 +1,448 @@
-fn seperate_groups(data: Vec<Vec<char>>) -> Vec<Vec<Vec<char>>> {
-    let mut groups: Vec<Vec<Vec<char>>> = vec![vec![]];
-    let mut index = 0;
+//! Day 6: Custom Customs, count the questions each group answered.
+//!
+//! ```
+//! use adventofcode::days::{day06::Day06, Solution};
+//!
+//! let groups = Day06::parse("abc\n\na\nb\nc\n\nab\nac").unwrap();
+//! assert_eq!(Day06::part1(&groups), Some(9));
+//! assert_eq!(Day06::part2(&groups), Some(4));
+//! ```
 
-    for row in data {
-        if row.len() > 0 {
-            groups[index].push(row);
+use super::Solution;
+use crate::error::AocError;
+use crate::util;
+use std::{collections::BTreeMap, fmt};
+
+/// The questions `a` to `z` one person, or a whole group, answered yes to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AnswerSet(u32);
+
+impl AnswerSet {
+    pub const EMPTY: AnswerSet = AnswerSet(0);
+    pub const ALL: AnswerSet = AnswerSet((1 << 26) - 1);
+
+    /// One person's line of answers, only `a` to `z` allowed.
+    pub fn parse(line_number: usize, line: &str) -> Result<AnswerSet, AocError> {
+        let mut set = AnswerSet::EMPTY;
+        for (i, c) in line.chars().enumerate() {
+            if !set.insert(c) {
+                return Err(AocError::parse_at(
+                    line_number,
+                    i + 1,
+                    &c.to_string(),
+                    "expected a question a-z",
+                ));
+            }
+        }
+        Ok(set)
+    }
+
+    fn bit(question: char) -> Option<u32> {
+        if question.is_ascii_lowercase() {
+            Some(1 << (question as u32 - 'a' as u32))
         } else {
-            groups.push(vec![]);
-            index += 1
+            None
         }
     }
 
-    groups
+    /// Add `question`, false if it isn't `a` to `z`.
+    pub fn insert(&mut self, question: char) -> bool {
+        match AnswerSet::bit(question) {
+            Some(bit) => {
+                self.0 |= bit;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(self, question: char) -> bool {
+        AnswerSet::bit(question).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    pub fn union(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & !other.0)
+    }
+
+    pub fn symmetric_difference(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 ^ other.0)
+    }
+
+    /// Number of questions in the set.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The questions in alphabetical order.
+    pub fn iter(self) -> impl Iterator<Item = char> {
+        ('a'..='z').filter(move |&c| self.contains(c))
+    }
+}
+
+impl fmt::Display for AnswerSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter().try_for_each(|c| write!(f, "{}", c))
+    }
 }
 
-pub fn part1(data: Vec<Vec<char>>) -> usize {
-    let groups = seperate_groups(data);
-    let mut total = 0;
+/// The answers of each person in a group.
+pub type Group = Vec<AnswerSet>;
 
-    for group in groups {
-        let mut answered = vec![];
+pub struct Day06;
 
-        for person in group {
-            for answer in person {
-                if !answered.contains(&answer) {
-                    answered.push(answer);
-                }
-            }
+impl Solution for Day06 {
+    type Input<'a> = Vec<Group>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Group>, AocError> {
+        seperate_groups(input)
+    }
+
+    fn part1(input: &Vec<Group>) -> Option<usize> {
+        Some(part1(input))
+    }
+
+    fn part2(input: &Vec<Group>) -> Option<usize> {
+        Some(part2(input))
+    }
+}
+
+fn seperate_groups(data: &str) -> Result<Vec<Group>, AocError> {
+    util::records(data)
+        .map(|record| {
+            record
+                .numbered_lines()
+                .map(|(line_number, row)| AnswerSet::parse(line_number, row))
+                .collect()
+        })
+        .collect()
+}
+
+/// How many people in `group` answered each question, `a` first.
+pub fn question_counts(group: &[AnswerSet]) -> [usize; 26] {
+    let mut counts = [0; 26];
+    for person in group {
+        for question in person.iter() {
+            counts[(question as u8 - b'a') as usize] += 1;
+        }
+    }
+    counts
+}
+
+/// Which questions of a group count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    /// Answered by anyone.
+    Any,
+    /// Answered by everyone, nothing for an empty group.
+    All,
+    /// Answered by exactly this many people, `Exactly(0)` is the questions
+    /// nobody answered.
+    Exactly(usize),
+    /// Answered by more than half the group.
+    Majority,
+}
+
+impl Aggregate {
+    pub fn apply(self, group: &[AnswerSet]) -> AnswerSet {
+        match self {
+            Aggregate::Any => group.iter().fold(AnswerSet::EMPTY, |a, &b| a.union(b)),
+            Aggregate::All if group.is_empty() => AnswerSet::EMPTY,
+            Aggregate::All => group.iter().fold(AnswerSet::ALL, |a, &b| a.intersection(b)),
+            Aggregate::Exactly(k) => counted(group, |count| count == k),
+            Aggregate::Majority => counted(group, |count| count * 2 > group.len()),
         }
+    }
+
+    /// The questions that count for each group.
+    pub fn per_group(self, groups: &[Group]) -> Vec<AnswerSet> {
+        groups.iter().map(|group| self.apply(group)).collect()
+    }
+
+    /// Sum over groups of the questions that count.
+    pub fn total(self, groups: &[Group]) -> usize {
+        groups.iter().map(|group| self.apply(group).len()).sum()
+    }
+}
 
-        total += answered.len();
+fn counted(group: &[AnswerSet], keep: impl Fn(usize) -> bool) -> AnswerSet {
+    let mut set = AnswerSet::EMPTY;
+    for (question, &count) in ('a'..='z').zip(&question_counts(group)) {
+        if keep(count) {
+            set.insert(question);
+        }
     }
+    set
+}
 
-    total
+/// The questions answered most and least often in `counts`, ignoring the
+/// ones nobody answered.
+fn extremes(counts: &[usize; 26]) -> (AnswerSet, AnswerSet) {
+    let answered = || counts.iter().copied().filter(|&count| count > 0);
+    let (max, min) = (answered().max(), answered().min());
+    let with_count = |target| {
+        let mut set = AnswerSet::EMPTY;
+        for (question, &count) in ('a'..='z').zip(counts) {
+            if Some(count) == target {
+                set.insert(question);
+            }
+        }
+        set
+    };
+    (with_count(max), with_count(min))
 }
 
-pub fn part2(data: Vec<Vec<char>>) -> usize {
-    let groups = seperate_groups(data);
-    let mut total = 0;
+/// Question frequencies for one group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupStats {
+    pub size: usize,
+    /// People answering each question, `a` first.
+    pub counts: [usize; 26],
+    pub any: usize,
+    pub all: usize,
+}
 
-    for group in groups {
-        let mut answered: Vec<char> = vec![];
+impl GroupStats {
+    pub fn new(group: &[AnswerSet]) -> GroupStats {
+        GroupStats {
+            size: group.len(),
+            counts: question_counts(group),
+            any: Aggregate::Any.apply(group).len(),
+            all: Aggregate::All.apply(group).len(),
+        }
+    }
 
-        // For each answer the first person in group gave...
-        for answer in &group[0] {
-            let mut in_all = true;
+    /// The most and least answered questions, all of them on a tie.
+    pub fn extremes(&self) -> (AnswerSet, AnswerSet) {
+        extremes(&self.counts)
+    }
+}
+
+/// Question frequencies for every group and across all of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub groups: Vec<GroupStats>,
+    pub counts: [usize; 26],
+}
 
-            for person in &group[1..] {
-                if !person.contains(&answer) {
-                    in_all = false;
-                }
+impl Stats {
+    pub fn new(groups: &[Group]) -> Stats {
+        let groups: Vec<GroupStats> = groups.iter().map(|group| GroupStats::new(group)).collect();
+        let mut counts = [0; 26];
+        for group in &groups {
+            for (total, count) in counts.iter_mut().zip(&group.counts) {
+                *total += count;
             }
+        }
+
+        Stats { groups, counts }
+    }
+
+    pub fn extremes(&self) -> (AnswerSet, AnswerSet) {
+        extremes(&self.counts)
+    }
+
+    /// Number of groups of each size.
+    pub fn sizes(&self) -> BTreeMap<usize, usize> {
+        distribution(self.groups.iter().map(|group| group.size))
+    }
+
+    /// Number of groups by how many questions everyone in them answered.
+    pub fn all_yes(&self) -> BTreeMap<usize, usize> {
+        distribution(self.groups.iter().map(|group| group.all))
+    }
 
-            if in_all {
-                answered.push(answer.clone());
+    /// A row per group then a `total` row, with the columns
+    /// `group,size,any,all,most,least,a,...,z`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("group,size,any,all,most,least");
+        for question in 'a'..='z' {
+            csv.push(',');
+            csv.push(question);
+        }
+        csv.push('\n');
+
+        let mut row = |label: &str, size, any, all, (most, least), counts: &[usize; 26]| {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}",
+                label, size, any, all, most, least
+            ));
+            for count in counts {
+                csv.push_str(&format!(",{}", count));
             }
+            csv.push('\n');
+        };
+
+        for (i, group) in self.groups.iter().enumerate() {
+            let label = (i + 1).to_string();
+            row(
+                &label,
+                group.size,
+                group.any,
+                group.all,
+                group.extremes(),
+                &group.counts,
+            );
         }
+        let sum = |field: fn(&GroupStats) -> usize| self.groups.iter().map(field).sum::<usize>();
+        row(
+            "total",
+            sum(|group| group.size),
+            sum(|group| group.any),
+            sum(|group| group.all),
+            self.extremes(),
+            &self.counts,
+        );
+
+        csv
+    }
+}
 
-        total += answered.len();
+fn distribution(values: impl Iterator<Item = usize>) -> BTreeMap<usize, usize> {
+    let mut counts = BTreeMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
     }
+    counts
+}
+
+/// A summary across all groups, see [`Stats::to_csv`] for every group.
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |counts: BTreeMap<usize, usize>| {
+            counts
+                .iter()
+                .map(|(value, groups)| format!("{}: {}", value, groups))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let (most, least) = self.extremes();
 
-    total
+        writeln!(
+            f,
+            "groups: {}, people: {}",
+            self.groups.len(),
+            self.groups.iter().map(|group| group.size).sum::<usize>()
+        )?;
+        writeln!(f, "most common: {}, least common: {}", most, least)?;
+        writeln!(f, "group sizes: {}", list(self.sizes()))?;
+        writeln!(f, "all yes counts: {}", list(self.all_yes()))?;
+        write!(f, "questions:")?;
+        for (question, count) in ('a'..='z').zip(&self.counts) {
+            write!(f, " {} {}", question, count)?;
+        }
+        writeln!(f)
+    }
+}
+
+/// Sum over groups of the questions anyone answered.
+pub fn part1(groups: &[Group]) -> usize {
+    Aggregate::Any.total(groups)
+}
+
+/// Sum over groups of the questions everyone answered.
+pub fn part2(groups: &[Group]) -> usize {
+    Aggregate::All.total(groups)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util;
+    use crate::input;
+
+    fn set(answers: &str) -> AnswerSet {
+        AnswerSet::parse(1, answers).unwrap()
+    }
 
     #[test]
     fn d6part1test() {
-        let data = util::grid_from_file("./data/day6.txt");
-        assert_eq!(6799, part1(data));
+        let data = Day06::parse(input::embedded(6).unwrap()).unwrap();
+        assert_eq!(6799, part1(&data));
     }
     #[test]
     fn d6part2test() {
-        let data = util::grid_from_file("./data/day6.txt");
-        assert_eq!(3354, part2(data));
+        let data = Day06::parse(input::embedded(6).unwrap()).unwrap();
+        assert_eq!(3354, part2(&data));
+    }
+
+    #[test]
+    fn d6trailingblanktest() {
+        let data = Day06::parse("ab\r\nb\r\n\r\n\r\nc\r\n\r\n").unwrap();
+        assert_eq!(2, data.len());
+        assert_eq!(2, part2(&data));
+    }
+
+    #[test]
+    fn d6answersettest() {
+        let (abc, bcd) = (set("abc"), set("cdb"));
+
+        assert_eq!("abcd", abc.union(bcd).to_string());
+        assert_eq!("bc", abc.intersection(bcd).to_string());
+        assert_eq!("a", abc.difference(bcd).to_string());
+        assert_eq!("ad", abc.symmetric_difference(bcd).to_string());
+        assert_eq!(26, AnswerSet::ALL.len());
+        assert!(AnswerSet::EMPTY.is_empty());
+        assert_eq!(
+            "line 2, column 2: expected a question a-z: \"A\"",
+            Day06::parse("ab\naA").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn d6statstest() {
+        let groups = Day06::parse("abc\nab\na\n\nxy\n\nb").unwrap();
+        let stats = Stats::new(&groups);
+
+        assert_eq!((set("ab"), set("cxy")), stats.extremes());
+        assert_eq!((set("a"), set("c")), stats.groups[0].extremes());
+        assert_eq!(
+            vec![(1, 2), (3, 1)],
+            stats.sizes().into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 2), (2, 1)],
+            stats.all_yes().into_iter().collect::<Vec<_>>()
+        );
+
+        let csv = stats.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(5, lines.len());
+        assert!(lines[0].starts_with("group,size,any,all,most,least,a,b,c,d,"));
+        assert!(lines[1].starts_with("1,3,3,1,a,c,3,2,1,0,"));
+        assert!(lines[4].starts_with("total,5,6,4,ab,cxy,3,3,1,0,"));
+        assert!(stats.to_string().contains("group sizes: 1: 2, 3: 1"));
+    }
+
+    #[test]
+    fn d6aggregatetest() {
+        let groups = Day06::parse("abc\nab\na\nz\n\nxy").unwrap();
+        let per_group = |aggregate: Aggregate| {
+            aggregate
+                .per_group(&groups)
+                .iter()
+                .map(|set| set.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec!["abcz", "xy"], per_group(Aggregate::Any));
+        assert_eq!(vec!["", "xy"], per_group(Aggregate::All));
+        assert_eq!(vec!["cz", "xy"], per_group(Aggregate::Exactly(1)));
+        assert_eq!(vec!["a", "xy"], per_group(Aggregate::Majority));
+        assert_eq!(3, Aggregate::Majority.total(&groups));
+        assert_eq!(22, Aggregate::Exactly(0).apply(&groups[0]).len());
+        assert_eq!(AnswerSet::EMPTY, Aggregate::All.apply(&[]));
     }
 }
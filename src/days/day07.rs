@@ -1,126 +1,366 @@
+//! Day 7: Handy Haversacks, follow the rules for which bags hold which.
+//!
+//! ```
+//! use adventofcode::BagRules;
+//!
+//! let rules: BagRules = "\
+//! light red bags contain 1 bright white bag, 2 muted yellow bags.
+//! bright white bags contain 1 shiny gold bag.
+//! muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+//! shiny gold bags contain 2 faded blue bags.
+//! faded blue bags contain no other bags."
+//!     .parse()
+//!     .unwrap();
+//!
+//! assert!(rules.bag_can_contain_bag("light red", "shiny gold"));
+//! assert_eq!(rules.sum_containment("shiny gold"), Some(2));
+//! ```
+
+use super::Solution;
+use crate::error::AocError;
 use regex::Regex;
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+/// A bag's colour, e.g. `shiny gold`.
+pub type Bag = String;
+
+/// A bag's index in [`BagRules`], in the order the rules were read.
+pub type BagId = usize;
+
+pub struct Day07;
 
-type Bag = String;
+impl Solution for Day07 {
+    type Input<'a> = BagRules;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<BagRules, AocError> {
+        BagRules::create_from_ruleset(input)
+    }
+
+    fn part1(input: &BagRules) -> Option<usize> {
+        part1(input)
+    }
 
-#[derive(Debug)]
-struct Containable {
-    bag: Bag,
-    total: u32,
+    fn part2(input: &BagRules) -> Option<u64> {
+        part2(input)
+    }
+}
+
+/// `total` bags of colour `bag`, held directly by another bag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Containable {
+    pub bag: BagId,
+    pub total: u32,
 }
 
-#[derive(Debug)]
-struct BagRules {
-    pub bags: Vec<Bag>,
-    pub containment_rules: HashMap<Bag, Vec<Containable>>,
+/// The rules as a graph: an edge from each bag to every bag it directly
+/// holds, and the same edges reversed. Bags can't end up inside themselves,
+/// so the graph has no cycles and the nested counts are worked out once up
+/// front.
+#[derive(Debug, Clone)]
+pub struct BagRules {
+    bags: Vec<Bag>,
+    ids: HashMap<Bag, BagId>,
+    contents: Vec<Vec<Containable>>,
+    holders: Vec<Vec<BagId>>,
+    /// Outermost bags first.
+    order: Vec<BagId>,
+    /// Bags inside each bag, counting nested ones, `None` past `u64::MAX`.
+    nested: Vec<Option<u64>>,
 }
 
 impl BagRules {
-    pub fn create_from_ruleset(rules: Vec<String>) -> BagRules {
+    /// One rule per line, `<colour> bags contain <count> <colour> bags, ...`.
+    pub fn create_from_ruleset(rules: &str) -> Result<BagRules, AocError> {
         let line_regex: Regex =
             Regex::new(r"^(.*) bags contain ((\d.*? bags?)|no other bags).$").unwrap();
         let content_regex: Regex = Regex::new(r"^(\d) (.*) bags?$").unwrap();
 
-        let mut bag_rules = BagRules {
-            bags: Vec::new(),
-            containment_rules: HashMap::new(),
-        };
+        let lines: Vec<&str> = rules.lines().collect();
+        let mut bags = vec![];
+        let mut ids = HashMap::new();
+        let mut named_contents = vec![];
 
-        for plain_rule in rules {
-            let rule = line_regex.captures(&plain_rule).unwrap();
+        for (line_number, plain_rule) in lines.iter().enumerate() {
+            let malformed = |reason| AocError::parse(line_number + 1, plain_rule, reason);
+            let rule = line_regex
+                .captures(plain_rule)
+                .ok_or_else(|| malformed(String::from("expected \"<colour> bags contain ...\"")))?;
 
             let line_name = rule.get(1).map_or("", |m| m.as_str());
-            let no_content = rule.get(2).map_or("", |m| m.as_str());
             let content = rule.get(3).map_or("", |m| m.as_str());
 
-            let contained = {
-                if no_content == "no other bags" {
-                    vec![]
-                } else {
-                    let mut item_names: Vec<Containable> = vec![];
+            let mut contained = vec![];
+            if !content.is_empty() {
+                for containable in content.split(", ") {
+                    let item = content_regex.captures(containable).ok_or_else(|| {
+                        malformed(String::from("expected \"<count> <colour> bags\""))
+                    })?;
+                    let total = item[1]
+                        .parse::<u32>()
+                        .map_err(|_| malformed(String::from("bag count is not a number")))?;
+                    contained.push((item.get(2).map_or("", |m| m.as_str()), total));
+                }
+            }
 
-                    for containable in content.split(", ") {
-                        let item = content_regex.captures(containable).unwrap();
-                        let item_count = item.get(1).map_or("", |m| m.as_str());
-                        let item_name = item.get(2).map_or("", |m| m.as_str());
+            if ids.insert(String::from(line_name), bags.len()).is_some() {
+                return Err(malformed(format!("second rule for {} bags", line_name)));
+            }
+            bags.push(String::from(line_name));
+            named_contents.push(contained);
+        }
 
-                        item_names.push(Containable {
-                            bag: String::from(item_name),
-                            total: item_count.parse::<u32>().unwrap(),
-                        });
-                    }
+        // Every bag mentioned inside a rule needs a rule of its own, or the queries below can't follow it
+        let mut contents = vec![];
+        let mut holders = vec![vec![]; bags.len()];
+        for (id, contained) in named_contents.into_iter().enumerate() {
+            let mut edges = vec![];
+            for (name, total) in contained {
+                let bag = *ids.get(name).ok_or_else(|| {
+                    AocError::parse(id + 1, lines[id], format!("no rule for {} bags", name))
+                })?;
+                holders[bag].push(id);
+                edges.push(Containable { bag, total });
+            }
+            contents.push(edges);
+        }
 
-                    item_names
-                }
-            };
+        let order = topological_order(&contents, &holders)
+            .map_err(|id| AocError::parse(id + 1, lines[id], "bag ends up inside itself"))?;
 
-            bag_rules.bags.push(String::from(line_name));
-            bag_rules
-                .containment_rules
-                .insert(String::from(line_name), contained);
+        // Innermost bags first, so everything a bag holds is already done
+        let mut nested = vec![Some(0u64); bags.len()];
+        for &id in order.iter().rev() {
+            for containable in &contents[id] {
+                let child = containable.bag;
+                nested[id] = nested[child]
+                    .and_then(|count| count.checked_add(1))
+                    .and_then(|count| count.checked_mul(u64::from(containable.total)))
+                    .and_then(|count| nested[id]?.checked_add(count));
+            }
         }
 
-        bag_rules
+        Ok(BagRules {
+            bags,
+            ids,
+            contents,
+            holders,
+            order,
+            nested,
+        })
+    }
+
+    /// Every colour, indexed by [`BagId`].
+    pub fn bags(&self) -> &[Bag] {
+        &self.bags
+    }
+
+    pub fn id(&self, bag: &str) -> Option<BagId> {
+        self.ids.get(bag).copied()
+    }
+
+    /// The bags `bag` directly holds.
+    pub fn contents(&self, bag: BagId) -> &[Containable] {
+        &self.contents[bag]
+    }
+
+    /// The bags directly holding `bag`.
+    pub fn holders(&self, bag: BagId) -> &[BagId] {
+        &self.holders[bag]
+    }
+
+    /// Every bag, each one before all the bags it holds.
+    pub fn topological_order(&self) -> &[BagId] {
+        &self.order
     }
 
-    pub fn bag_can_contain_bag(&self, bag: &Bag, target: &Bag) -> bool {
-        for containable in self.containment_rules.get(bag).unwrap() {
-            if &containable.bag == target {
-                return true;
-            } else {
-                if self.bag_can_contain_bag(&containable.bag, &target) {
+    /// Whether `bag` holds `target`, directly or inside other bags. False
+    /// when either colour has no rule.
+    pub fn bag_can_contain_bag(&self, bag: &str, target: &str) -> bool {
+        let (bag, target) = match (self.id(bag), self.id(target)) {
+            (Some(bag), Some(target)) => (bag, target),
+            _ => return false,
+        };
+
+        let mut seen = vec![false; self.bags.len()];
+        let mut stack = vec![bag];
+        while let Some(bag) = stack.pop() {
+            for containable in &self.contents[bag] {
+                if containable.bag == target {
                     return true;
                 }
+                if !seen[containable.bag] {
+                    seen[containable.bag] = true;
+                    stack.push(containable.bag);
+                }
             }
         }
 
         false
     }
 
-    pub fn sum_containment(&self, bag: &Bag) -> u32 {
-        let mut total = 0;
+    /// Total bags inside `bag`, counting nested bags, `None` without a rule for
+    /// it or when there are more than fit in a `u64`.
+    pub fn sum_containment(&self, bag: &str) -> Option<u64> {
+        self.nested[self.id(bag)?]
+    }
+
+    /// Every bag that can end up holding `target`, nearest first.
+    pub fn containers_of(&self, target: BagId) -> Vec<BagId> {
+        let mut seen = vec![false; self.bags.len()];
+        let mut queue = VecDeque::from(vec![target]);
+        let mut containers = vec![];
 
-        for containable in self.containment_rules.get(bag).unwrap() {
-            total += containable.total;
-            total += containable.total * self.sum_containment(&containable.bag);
+        while let Some(bag) = queue.pop_front() {
+            for &holder in &self.holders[bag] {
+                if !seen[holder] {
+                    seen[holder] = true;
+                    containers.push(holder);
+                    queue.push_back(holder);
+                }
+            }
         }
 
-        total
+        containers
     }
 }
 
-pub fn part1(rules: Vec<String>) -> u32 {
-    let bag_rules = BagRules::create_from_ruleset(rules);
-    let mut total = 0;
+/// Kahn's algorithm from the bags nothing holds, or the id of a bag on a
+/// cycle when there's no such order.
+fn topological_order(
+    contents: &[Vec<Containable>],
+    holders: &[Vec<BagId>],
+) -> Result<Vec<BagId>, BagId> {
+    let mut waiting: Vec<usize> = holders.iter().map(Vec::len).collect();
+    let mut ready: VecDeque<BagId> = (0..holders.len()).filter(|&id| waiting[id] == 0).collect();
+    let mut order = vec![];
 
-    for bag in &bag_rules.bags {
-        if bag_rules.bag_can_contain_bag(bag, &String::from("shiny gold")) {
-            total += 1;
+    while let Some(id) = ready.pop_front() {
+        order.push(id);
+        for containable in &contents[id] {
+            waiting[containable.bag] -= 1;
+            if waiting[containable.bag] == 0 {
+                ready.push_back(containable.bag);
+            }
         }
     }
 
-    total
+    // A bag left waiting still has a holder left waiting, so following holders
+    // from one of them has to come back round to a bag it has already seen
+    let mut id = match (0..holders.len()).find(|&id| waiting[id] > 0) {
+        Some(id) => id,
+        None => return Ok(order),
+    };
+    let mut seen = vec![false; holders.len()];
+    while !seen[id] {
+        seen[id] = true;
+        id = *holders[id]
+            .iter()
+            .find(|&&holder| waiting[holder] > 0)
+            .expect("a waiting bag has a waiting holder");
+    }
+
+    Err(id)
 }
 
-pub fn part2(rules: Vec<String>) -> u32 {
-    let bag_rules = BagRules::create_from_ruleset(rules);
-    bag_rules.sum_containment(&String::from("shiny gold"))
+impl FromStr for BagRules {
+    type Err = AocError;
+
+    fn from_str(rules: &str) -> Result<BagRules, AocError> {
+        BagRules::create_from_ruleset(rules)
+    }
+}
+
+/// Colours that can eventually hold a shiny gold bag.
+pub fn part1(bag_rules: &BagRules) -> Option<usize> {
+    bag_rules
+        .id("shiny gold")
+        .map(|gold| bag_rules.containers_of(gold).len())
+}
+
+/// Bags needed inside a shiny gold bag.
+pub fn part2(bag_rules: &BagRules) -> Option<u64> {
+    bag_rules.sum_containment("shiny gold")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util;
+    use crate::input;
 
     #[test]
     fn d7part1test() {
-        let data = util::lines_from_file("./data/day7.txt");
-        assert_eq!(119, part1(data));
+        let data = Day07::parse(input::embedded(7).unwrap()).unwrap();
+        assert_eq!(Some(119), part1(&data));
     }
 
     #[test]
     fn d7part2test() {
-        let data = util::lines_from_file("./data/day7.txt");
-        assert_eq!(155802, part2(data));
+        let data = Day07::parse(input::embedded(7).unwrap()).unwrap();
+        assert_eq!(Some(155802), part2(&data));
+    }
+
+    #[test]
+    fn d7graphtest() {
+        let rules = Day07::parse(include_str!("../../data/examples/day7_example.txt")).unwrap();
+        let id = |bag| rules.id(bag).unwrap();
+        let names = |ids: &[BagId]| {
+            ids.iter()
+                .map(|&id| &rules.bags()[id][..])
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec!["bright white", "muted yellow", "light red", "dark orange"],
+            names(&rules.containers_of(id("shiny gold")))
+        );
+        assert_eq!(
+            names(rules.holders(id("shiny gold"))),
+            vec!["bright white", "muted yellow"]
+        );
+        assert!(!rules.bag_can_contain_bag("shiny gold", "light red"));
+        assert!(rules.bag_can_contain_bag("dark orange", "dotted black"));
+        assert_eq!(Some(0), rules.sum_containment("faded blue"));
+
+        let position = |bag| rules.topological_order().iter().position(|&b| b == id(bag));
+        for &bag in rules.topological_order() {
+            for containable in rules.contents(bag) {
+                assert!(position(&rules.bags()[bag]) < position(&rules.bags()[containable.bag]));
+            }
+        }
+
+        let chain: String = (0..26)
+            .map(|i| format!("c{} bags contain 9 c{} bags.\n", i, i + 1))
+            .chain(Some(String::from("c26 bags contain no other bags.")))
+            .collect();
+        let chain = Day07::parse(&chain).unwrap();
+        assert_eq!(None, chain.sum_containment("c0"));
+        assert_eq!(Some(9), chain.sum_containment("c25"));
+    }
+
+    #[test]
+    fn d7malformedtest() {
+        let err = |rules: &str| rules.parse::<BagRules>().unwrap_err().to_string();
+
+        assert_eq!(
+            "line 1: bag ends up inside itself: \"a bags contain 1 b bag.\"",
+            err("a bags contain 1 b bag.\nb bags contain 1 a bag.\nc bags contain no other bags.")
+        );
+        assert_eq!(
+            "line 2: bag ends up inside itself: \"a bags contain 1 b bag, 1 c bag.\"",
+            err("c bags contain no other bags.\na bags contain 1 b bag, 1 c bag.\nb bags contain 1 a bag.")
+        );
+        assert_eq!(
+            "line 1: no rule for b bags: \"a bags contain 1 b bag.\"",
+            err("a bags contain 1 b bag.")
+        );
+        assert_eq!(
+            "line 2: second rule for a bags: \"a bags contain no other bags.\"",
+            err("a bags contain no other bags.\na bags contain no other bags.")
+        );
     }
 }
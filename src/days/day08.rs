@@ -1,20 +1,57 @@
+//! Day 8: Handheld Halting, run boot code until it loops or terminates.
+//!
+//! ```
+//! use adventofcode::Program;
+//!
+//! let mut program: Program = "nop +0\nacc +1\njmp -2".parse().unwrap();
+//! assert_eq!(program.run(), Some(1));
+//! assert_eq!(program.accumulator, 1);
+//! assert!(!program.terminated());
+//! ```
+
+use super::Solution;
+use crate::error::AocError;
 use regex::Regex;
+use std::str::FromStr;
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Program;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> Result<Program, AocError> {
+        Program::new(input)
+    }
+
+    fn part1(input: &Program) -> Option<isize> {
+        part1(input)
+    }
+
+    fn part2(input: &Program) -> Option<isize> {
+        part2(input)
+    }
+}
+
+/// Instruction kinds of the handheld's boot code.
 #[derive(PartialEq, Debug, Clone)]
-enum Operation {
-    JMP,
-    ACC,
-    NOP,
+pub enum Operation {
+    Jmp,
+    Acc,
+    Nop,
 }
 
+/// A single instruction and its signed argument.
 #[derive(Clone, Debug)]
-struct Line {
+pub struct Line {
     operation: Operation,
     value: isize,
 }
 
+/// Boot code plus the state of running it.
 #[derive(Clone, Debug)]
-struct Program {
+pub struct Program {
     lines: Vec<Line>,
     visited: Vec<isize>,
     location: isize,
@@ -22,46 +59,60 @@ struct Program {
 }
 
 impl Line {
-    pub fn create_from_string(line: &String) -> Line {
-        let line_regex = Regex::new(r"(\w{3}) ([+-])(\d+)").unwrap();
-        let capture = line_regex.captures(line).unwrap();
+    /// Parse an instruction such as `acc +3` with the regex [`Program::new`]
+    /// compiles, `line_number` is used in errors.
+    pub fn create_from_string(
+        line_regex: &Regex,
+        line_number: usize,
+        line: &str,
+    ) -> Result<Line, AocError> {
+        let malformed = |reason| AocError::parse(line_number, line, reason);
+        let capture = line_regex
+            .captures(line)
+            .ok_or_else(|| malformed("expected \"<op> <+|-><number>\""))?;
 
         let operation = match capture.get(1).map_or("", |m| m.as_str()) {
-            "jmp" => Operation::JMP,
-            "acc" => Operation::ACC,
-            _ => Operation::NOP,
+            "jmp" => Operation::Jmp,
+            "acc" => Operation::Acc,
+            "nop" => Operation::Nop,
+            _ => return Err(malformed("unknown operation")),
         };
         let modifier = capture.get(2).map_or("", |m| m.as_str());
         let value = capture
             .get(3)
             .map_or("", |m| m.as_str())
             .parse::<isize>()
-            .unwrap();
+            .map_err(|_| malformed("argument is out of range"))?;
 
-        Line {
+        Ok(Line {
             operation,
             value: if modifier == "+" { value } else { -value },
-        }
+        })
     }
 }
 
 impl Program {
-    pub fn new(data: Vec<String>) -> Program {
+    /// One instruction per line.
+    pub fn new(source: &str) -> Result<Program, AocError> {
+        let line_regex = Regex::new(r"^(\w{3}) ([+-])(\d+)$").unwrap();
         let mut lines = vec![];
 
-        for line in data {
-            lines.push(Line::create_from_string(&line))
+        for (index, line) in source.lines().enumerate() {
+            lines.push(Line::create_from_string(&line_regex, index + 1, line)?)
         }
 
-        Program {
+        Ok(Program {
             lines,
             location: 0,
             accumulator: 0,
             visited: vec![],
-        }
+        })
     }
 
-    pub fn run(&mut self) {
+    /// Run until an instruction is about to execute a second time, or the
+    /// program ends. The accumulator then, `None` if it or the location
+    /// overflowed on the way.
+    pub fn run(&mut self) -> Option<isize> {
         loop {
             if self.location as usize >= self.lines.len() {
                 break;
@@ -76,11 +127,11 @@ impl Program {
             }
 
             match line.operation {
-                Operation::JMP => {
-                    self.location += line.value;
+                Operation::Jmp => {
+                    self.location = self.location.checked_add(line.value)?;
                 }
-                Operation::ACC => {
-                    self.accumulator += line.value;
+                Operation::Acc => {
+                    self.accumulator = self.accumulator.checked_add(line.value)?;
                     self.location += 1;
                 }
                 _ => {
@@ -88,34 +139,44 @@ impl Program {
                 }
             }
         }
+
+        Some(self.accumulator)
+    }
+
+    /// Whether the last run ended by stepping past the final instruction.
+    pub fn terminated(&self) -> bool {
+        self.location >= self.lines.len() as isize
     }
 }
 
-pub fn part1(data: Vec<String>) -> isize {
-    let mut program = Program::new(data);
-    program.run();
+impl FromStr for Program {
+    type Err = AocError;
 
-    program.accumulator
+    fn from_str(source: &str) -> Result<Program, AocError> {
+        Program::new(source)
+    }
 }
 
-pub fn part2(data: Vec<String>) -> Option<isize> {
-    let base_program = Program::new(data);
+/// Accumulator just before the first repeated instruction.
+pub fn part1(base_program: &Program) -> Option<isize> {
+    base_program.clone().run()
+}
 
+/// Accumulator once the program terminates after swapping one `jmp`/`nop`.
+pub fn part2(base_program: &Program) -> Option<isize> {
     for line in 0..base_program.lines.len() {
         let mut program = base_program.clone();
 
-        if program.lines[line].operation == Operation::NOP {
-            program.lines[line].operation = Operation::JMP;
-        }
-
-        if program.lines[line].operation == Operation::JMP {
-            program.lines[line].operation = Operation::NOP;
-        }
-
-        program.run();
+        program.lines[line].operation = match program.lines[line].operation {
+            Operation::Jmp => Operation::Nop,
+            Operation::Nop => Operation::Jmp,
+            Operation::Acc => continue,
+        };
 
-        if program.location >= program.lines.len() as isize {
-            return Some(program.accumulator);
+        if let Some(accumulator) = program.run() {
+            if program.terminated() {
+                return Some(accumulator);
+            }
         }
     }
 
@@ -125,16 +186,36 @@ pub fn part2(data: Vec<String>) -> Option<isize> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util;
+    use crate::input;
 
     #[test]
     fn d8part1test() {
-        let data = util::lines_from_file("./data/day8.txt");
-        assert_eq!(1928, part1(data));
+        let data = Day08::parse(input::embedded(8).unwrap()).unwrap();
+        assert_eq!(Some(1928), part1(&data));
     }
     #[test]
     fn d8part2test() {
-        let data = util::lines_from_file("./data/day8.txt");
-        assert_eq!(1319, part2(data).unwrap());
+        let data = Day08::parse(input::embedded(8).unwrap()).unwrap();
+        assert_eq!(1319, part2(&data).unwrap());
+    }
+
+    #[test]
+    fn d8swaptest() {
+        // Only turning the `nop` into a `jmp` gets past the end
+        let program = Day08::parse("nop +3\njmp -1\njmp -2\nacc +1").unwrap();
+        assert_eq!(Some(1), part2(&program));
+    }
+
+    #[test]
+    fn d8overflowtest() {
+        let program = Day08::parse("acc +9223372036854775807\nacc +1").unwrap();
+        assert_eq!(None, part1(&program));
+        assert_eq!(None, part2(&program));
+    }
+
+    #[test]
+    fn d8malformedtest() {
+        let err = Day08::parse("nop +0\nhcf +1").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: unknown operation: \"hcf +1\"");
     }
 }
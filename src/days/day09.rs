@@ -1,5 +1,38 @@
+//! Day 9: Encoding Error, find the number that breaks the XMAS cipher.
+//!
+//! ```
+//! use adventofcode::twosum;
+//!
+//! assert!(twosum(&[35, 20, 15, 25, 47], 40));
+//! assert!(!twosum(&[95, 102, 117, 150, 182], 127));
+//! ```
+
+use super::Solution;
+use crate::error::AocError;
+use crate::util;
 use std::collections::HashSet;
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = Vec<isize>;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> Result<Vec<isize>, AocError> {
+        util::parse_lines(input)
+    }
+
+    fn part1(input: &Vec<isize>) -> Option<isize> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<isize>) -> Option<isize> {
+        part2(input)
+    }
+}
+
+/// Whether two different entries of `data` add up to `target`.
 pub fn twosum(data: &[isize], target: isize) -> bool {
     let mut seen = HashSet::new();
 
@@ -13,20 +46,16 @@ pub fn twosum(data: &[isize], target: isize) -> bool {
     false
 }
 
-pub fn part1(data: &Vec<isize>) -> Option<isize> {
-    for (index, value) in data[25..].iter().enumerate() {
-        let available = &data[index..index + 25];
-
-        if !twosum(&available, *value) {
-            return Some(*value);
-        }
-    }
-
-    None
+/// First number that isn't the sum of two of the 25 before it.
+pub fn part1(data: &[isize]) -> Option<isize> {
+    data.windows(26)
+        .find(|window| !twosum(&window[..25], window[25]))
+        .map(|window| window[25])
 }
 
-pub fn part2(data: &Vec<isize>) -> Option<isize> {
-    let target = part1(&data).unwrap();
+/// Sum of the smallest and largest of the contiguous run adding up to the part 1 answer.
+pub fn part2(data: &[isize]) -> Option<isize> {
+    let target = part1(data)?;
 
     for (i, num1) in data.iter().enumerate() {
         let mut total = *num1;
@@ -53,17 +82,24 @@ pub fn part2(data: &Vec<isize>) -> Option<isize> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util;
+    use crate::input;
 
     #[test]
     fn d9part1test() {
-        let data = util::lines_from_file_as_isize("./data/day9.txt");
+        let data = Day09::parse(input::embedded(9).unwrap()).unwrap();
         assert_eq!(85848519, part1(&data).unwrap());
     }
 
     #[test]
     fn d9part2test() {
-        let data = util::lines_from_file_as_isize("./data/day9.txt");
+        let data = Day09::parse(input::embedded(9).unwrap()).unwrap();
         assert_eq!(13414198, part2(&data).unwrap());
     }
+
+    #[test]
+    fn d9shorttest() {
+        let data = Day09::parse("1\n2\n").unwrap();
+        assert_eq!(None, part1(&data));
+        assert_eq!(None, part2(&data));
+    }
 }
@@ -1,3 +1,9 @@
+use crate::error::AocError;
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -7,3 +13,125 @@ pub mod day06;
 pub mod day07;
 pub mod day08;
 pub mod day09;
+
+/// A puzzle day: parse the raw input once, then answer both parts from it.
+/// A part returns `None` when the input has no answer. The parsed input may
+/// borrow from the raw input.
+pub trait Solution {
+    type Input<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError>;
+    fn part1(input: &Self::Input<'_>) -> Option<Self::Answer1>;
+    fn part2(input: &Self::Input<'_>) -> Option<Self::Answer2>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: &'static [Part] = &[Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A formatted answer and how long the part took, not counting parsing.
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub time: Duration,
+}
+
+/// The result of running a day once on one input.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// A registered day, runnable on raw puzzle input without knowing its types.
+pub struct Day {
+    pub number: u32,
+    run: fn(&str, &[Part]) -> Result<Report, AocError>,
+}
+
+impl Day {
+    /// Parse `input` once and answer each of `parts` from it.
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<Report, AocError> {
+        (self.run)(input, parts)
+    }
+}
+
+/// What a part formats as when the input has no answer.
+pub const NO_ANSWER: &str = "no answer";
+
+fn format_answer<T: Display>(answer: Option<T>) -> String {
+    match answer {
+        Some(value) => value.to_string(),
+        None => String::from(NO_ANSWER),
+    }
+}
+
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Report, AocError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let value = match part {
+                Part::One => format_answer(S::part1(&parsed)),
+                Part::Two => format_answer(S::part2(&parsed)),
+            };
+            Answer {
+                part,
+                value,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Report {
+        parse_time,
+        answers,
+    })
+}
+
+const fn day<S: Solution>(number: u32) -> Day {
+    Day {
+        number,
+        run: run::<S>,
+    }
+}
+
+// Add new days here, the runner picks them up from this list.
+pub const DAYS: &[Day] = &[
+    day::<day01::Day01>(1),
+    day::<day02::Day02>(2),
+    day::<day03::Day03>(3),
+    day::<day04::Day04>(4),
+    day::<day05::Day05>(5),
+    day::<day06::Day06>(6),
+    day::<day07::Day07>(7),
+    day::<day08::Day08>(8),
+    day::<day09::Day09>(9),
+];
+
+/// The registered day with this number.
+pub fn get(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
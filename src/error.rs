@@ -0,0 +1,131 @@
+use std::{
+    error::Error,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub enum AocError {
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    Parse {
+        path: Option<PathBuf>,
+        line: usize,
+        /// Where on the line `text` starts, counting characters from 1.
+        column: Option<usize>,
+        text: String,
+        reason: String,
+    },
+    /// A manifest or config file that isn't the expected TOML.
+    Config {
+        path: Option<PathBuf>,
+        reason: String,
+    },
+}
+
+impl AocError {
+    /// A malformed line of puzzle input, `line` counts from 1.
+    pub fn parse(line: usize, text: &str, reason: impl Into<String>) -> AocError {
+        AocError::Parse {
+            path: None,
+            line,
+            column: None,
+            text: String::from(text),
+            reason: reason.into(),
+        }
+    }
+
+    /// A malformed part of a line, `text` starting at `column`.
+    pub fn parse_at(line: usize, column: usize, text: &str, reason: impl Into<String>) -> AocError {
+        AocError::Parse {
+            path: None,
+            line,
+            column: Some(column),
+            text: String::from(text),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn io(path: impl AsRef<Path>, source: io::Error) -> AocError {
+        AocError::Io {
+            path: Some(path.as_ref().to_path_buf()),
+            source,
+        }
+    }
+
+    /// Parsers and readers only see text, so the caller that opened the file fills the path in.
+    pub fn with_path(self, path: impl AsRef<Path>) -> AocError {
+        match self {
+            AocError::Io { path: None, source } => AocError::io(path, source),
+            AocError::Config { path: None, reason } => AocError::Config {
+                path: Some(path.as_ref().to_path_buf()),
+                reason,
+            },
+            AocError::Parse {
+                line,
+                column,
+                text,
+                reason,
+                ..
+            } => AocError::Parse {
+                path: Some(path.as_ref().to_path_buf()),
+                line,
+                column,
+                text,
+                reason,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "could not read {}: {}", path.display(), source),
+            AocError::Io { path: None, source } => write!(f, "could not read input: {}", source),
+            AocError::Parse {
+                path,
+                line,
+                column,
+                text,
+                reason,
+            } => {
+                match (path, column) {
+                    (Some(path), Some(column)) => {
+                        write!(f, "{}:{}:{}", path.display(), line, column)?
+                    }
+                    (Some(path), None) => write!(f, "{}:{}", path.display(), line)?,
+                    (None, Some(column)) => write!(f, "line {}, column {}", line, column)?,
+                    (None, None) => write!(f, "line {}", line)?,
+                }
+                write!(f, ": {}: {:?}", reason, text)
+            }
+            AocError::Config {
+                path: Some(path),
+                reason,
+            } => write!(f, "{}: {}", path.display(), reason),
+            AocError::Config { path: None, reason } => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(source: io::Error) -> AocError {
+        AocError::Io { path: None, source }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            AocError::Parse { .. } | AocError::Config { .. } => None,
+        }
+    }
+}
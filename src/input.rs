@@ -0,0 +1,119 @@
+use crate::{error::AocError, util};
+use std::{
+    borrow::Cow,
+    fmt,
+    fs::File,
+    io::{self, prelude::*, BufReader},
+    path::PathBuf,
+};
+
+// The checked-in puzzle inputs, compiled in so they can be used from anywhere.
+const EMBEDDED: &[(u32, &str)] = &[
+    (1, include_str!("../data/day1.txt")),
+    (2, include_str!("../data/day2.txt")),
+    (3, include_str!("../data/day3.txt")),
+    (4, include_str!("../data/day4.txt")),
+    (5, include_str!("../data/day5.txt")),
+    (6, include_str!("../data/day6.txt")),
+    (7, include_str!("../data/day7.txt")),
+    (8, include_str!("../data/day8.txt")),
+    (9, include_str!("../data/day9.txt")),
+];
+
+/// The checked-in puzzle input for a day.
+pub fn embedded(day: u32) -> Option<&'static str> {
+    EMBEDDED
+        .iter()
+        .find(|(number, _)| *number == day)
+        .map(|(_, text)| *text)
+}
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource<'a> {
+    Path(PathBuf),
+    Stdin,
+    Text(&'a str),
+    /// The checked-in input for a day, see [`embedded`].
+    Embedded(u32),
+}
+
+impl<'a> InputSource<'a> {
+    /// A command-line argument, `-` means stdin and anything else a path.
+    pub fn from_arg(arg: &str) -> InputSource<'static> {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+
+    fn embedded_text(day: u32) -> Result<&'static str, AocError> {
+        embedded(day).ok_or_else(|| {
+            AocError::from(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no embedded input for day {}", day),
+            ))
+        })
+    }
+
+    /// The whole input, borrowed when it's already in memory.
+    pub fn read(&self) -> Result<Cow<'a, str>, AocError> {
+        match self {
+            InputSource::Path(path) => util::read_file(path).map(Cow::Owned),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin().lock().read_to_string(&mut contents)?;
+                Ok(Cow::Owned(contents))
+            }
+            InputSource::Text(text) => Ok(Cow::Borrowed(text)),
+            InputSource::Embedded(day) => InputSource::embedded_text(*day).map(Cow::Borrowed),
+        }
+    }
+
+    /// The input as a reader, for the streaming helpers in [`util`].
+    pub fn reader(&self) -> Result<Box<dyn BufRead + 'a>, AocError> {
+        match self {
+            InputSource::Path(path) => File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|err| AocError::io(path, err)),
+            InputSource::Stdin => Ok(Box::new(BufReader::new(io::stdin()))),
+            InputSource::Text(text) => Ok(Box::new(text.as_bytes())),
+            InputSource::Embedded(day) => {
+                Ok(Box::new(InputSource::embedded_text(*day)?.as_bytes()))
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputSource<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Text(_) => write!(f, "<text>"),
+            InputSource::Embedded(day) => write!(f, "data/day{}.txt", day),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_sources_test() {
+        let embedded = InputSource::Embedded(1).read().unwrap();
+        let text = InputSource::Text(&embedded).read().unwrap();
+        let path =
+            InputSource::Path(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/day1.txt"))
+                .read()
+                .unwrap();
+
+        assert_eq!(embedded, text);
+        assert_eq!(embedded, path);
+        assert_eq!(
+            embedded.lines().count(),
+            util::lines_iter(InputSource::Embedded(1).reader().unwrap()).count()
+        );
+    }
+}
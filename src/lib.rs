@@ -1,7 +1,36 @@
-#[allow(dead_code)]
-mod days;
+//! Advent of Code 2020 solutions, usable as a library.
+//!
+//! Every day lives in [`days`] as a module with a [`Solution`] implementation,
+//! along with the domain types and helpers it is built from. The most useful
+//! of those are re-exported here.
+//!
+//! ```
+//! use adventofcode::days::{day01::Day01, Solution};
+//!
+//! let expenses = Day01::parse("1721\n979\n366\n299\n675\n1456").unwrap();
+//! assert_eq!(Day01::part1(&expenses), Some(514579));
+//! ```
+//!
+//! Days can also be run by number through the [`days::DAYS`] registry,
+//! which is what the `adventofcode` binary does. Input can come from a file,
+//! stdin, a string or the puzzle inputs compiled into the crate, see
+//! [`InputSource`].
 
-#[allow(dead_code)]
-mod util;
+pub mod answers;
+pub mod days;
+pub mod error;
+pub mod input;
+pub mod util;
 
-// Nothin here yet, implementations and tests inside days/dayxx.rs
+pub use days::{
+    day02::{PasswordEntry, PasswordPolicy},
+    day03::traverse,
+    day04::Passport,
+    day07::BagRules,
+    day08::Program,
+    day09::twosum,
+    Solution,
+};
+pub use error::AocError;
+pub use input::InputSource;
+pub use util::Point;
@@ -0,0 +1,319 @@
+use adventofcode::{
+    answers::{self, Manifest, Status},
+    days::{
+        self,
+        day04::{self, Schema},
+        day06::{self, Stats},
+        Day, Part, Report, Solution,
+    },
+    InputSource,
+};
+use std::{env, path::PathBuf, process, time::Duration};
+
+// Without --input each day runs on its checked-in input
+const USAGE: &str = "usage: adventofcode [--day N] [--part 1|2|all] [--input PATH|-] [--time]
+       adventofcode verify [--manifest PATH]
+       adventofcode passports [--input PATH|-] [--schema PATH]
+       adventofcode customs [--input PATH|-] [--csv]";
+
+struct Args {
+    day: Option<u32>,
+    parts: &'static [Part],
+    input: Option<InputSource<'static>>,
+    time: bool,
+    help: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        day: None,
+        parts: Part::BOTH,
+        input: None,
+        time: false,
+        help: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+
+        match &arg[..] {
+            "--day" => {
+                let day = value()?;
+                parsed.day = Some(day.parse().map_err(|_| format!("invalid day {}", day))?);
+            }
+            "--part" => {
+                parsed.parts = match &value()?[..] {
+                    "1" => &[Part::One],
+                    "2" => &[Part::Two],
+                    "all" => Part::BOTH,
+                    other => return Err(format!("invalid part {}", other)),
+                }
+            }
+            "--input" => parsed.input = Some(InputSource::from_arg(&value()?)),
+            "--time" => parsed.time = true,
+            "-h" | "--help" => parsed.help = true,
+            other => return Err(format!("unknown argument {}\n{}", other, USAGE)),
+        }
+    }
+
+    if !parsed.help && parsed.day.is_none() && parsed.input.is_some() {
+        return Err(String::from("--input needs a --day"));
+    }
+
+    Ok(parsed)
+}
+
+fn run(day: &Day, parts: &[Part], source: InputSource) -> Option<Report> {
+    let report = source
+        .read()
+        .and_then(|input| day.run(&input, parts))
+        .map_err(|err| err.with_path(source.to_string()));
+
+    match report {
+        Ok(report) => Some(report),
+        Err(err) => {
+            eprintln!("day {}: {}", day.number, err);
+            None
+        }
+    }
+}
+
+fn print_answers(day: &Day, report: &Report) {
+    for answer in &report.answers {
+        println!("day {} part {}: {}", day.number, answer.part, answer.value);
+    }
+}
+
+fn print_times(reports: &[(&Day, Report)]) {
+    let mut rows = vec![[
+        String::from("day"),
+        String::from("part"),
+        String::from("answer"),
+        String::from("time"),
+    ]];
+    let mut total = Duration::default();
+
+    for (day, report) in reports {
+        rows.push([
+            day.number.to_string(),
+            String::from("parse"),
+            String::new(),
+            format!("{:?}", report.parse_time),
+        ]);
+        total += report.parse_time;
+
+        for answer in &report.answers {
+            rows.push([
+                day.number.to_string(),
+                answer.part.to_string(),
+                answer.value.clone(),
+                format!("{:?}", answer.time),
+            ]);
+            total += answer.time;
+        }
+    }
+    rows.push([
+        String::from("total"),
+        String::new(),
+        String::new(),
+        format!("{:?}", total),
+    ]);
+
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    for row in &rows {
+        println!(
+            "{:>w0$}  {:<w1$}  {:>w2$}  {:>w3$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+    }
+}
+
+// Check every registered day against the answers manifest, inputs are relative to it
+fn verify(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut manifest_path = answers::data_dir().join("answers.toml");
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--manifest" => {
+                manifest_path = PathBuf::from(args.next().ok_or("missing value for --manifest")?)
+            }
+            other => return Err(format!("unknown argument {}\n{}", other, USAGE)),
+        }
+    }
+
+    let manifest = Manifest::load(&manifest_path).map_err(|err| err.to_string())?;
+    let dir = manifest_path
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let checks = answers::verify(&manifest, &dir);
+
+    let mut counts = [0; 5];
+    for check in &checks {
+        let (index, label) = match check.status {
+            Status::Pass => (0, "ok"),
+            Status::Mismatch { .. } => (1, "FAIL"),
+            Status::Missing { .. } => (2, "MISSING"),
+            Status::Failed { .. } => (3, "ERROR"),
+            Status::NewlySolved { .. } => (4, "NEW"),
+        };
+        counts[index] += 1;
+        if index != 0 {
+            println!("{:<7} {}", label, check);
+        }
+    }
+
+    println!(
+        "{} passed, {} mismatched, {} missing, {} errors, {} newly solved",
+        counts[0], counts[1], counts[2], counts[3], counts[4]
+    );
+
+    Ok(checks.iter().all(|check| check.passed()))
+}
+
+// Say what's wrong with each day 4 passport, by default the checked-in ones
+fn passports(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut source = InputSource::Embedded(4);
+    let mut schema = Schema::passport();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+
+        match &arg[..] {
+            "--input" => source = InputSource::from_arg(&value()?),
+            "--schema" => schema = Schema::load(value()?).map_err(|err| err.to_string())?,
+            other => return Err(format!("unknown argument {}\n{}", other, USAGE)),
+        }
+    }
+
+    let passports = source
+        .read()
+        .and_then(|input| day04::parse_records(&input))
+        .map_err(|err| err.with_path(source.to_string()).to_string())?;
+
+    let mut valid = 0;
+    for (index, passport) in passports.iter().enumerate() {
+        let errors = schema.validate(passport);
+        if errors.is_empty() {
+            valid += 1;
+            println!("passport {}: valid", index + 1);
+        } else {
+            println!("passport {}: invalid", index + 1);
+            for err in errors {
+                println!("  {}", err);
+            }
+        }
+    }
+    println!("{} of {} passports valid", valid, passports.len());
+
+    Ok(valid == passports.len())
+}
+
+// Question statistics for the day 6 groups, a summary or a CSV row per group
+fn customs(mut args: impl Iterator<Item = String>) -> Result<bool, String> {
+    let mut source = InputSource::Embedded(6);
+    let mut csv = false;
+
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--input" => {
+                source = InputSource::from_arg(&args.next().ok_or("missing value for --input")?)
+            }
+            "--csv" => csv = true,
+            other => return Err(format!("unknown argument {}\n{}", other, USAGE)),
+        }
+    }
+
+    let groups = source
+        .read()
+        .and_then(|input| day06::Day06::parse(&input))
+        .map_err(|err| err.with_path(source.to_string()).to_string())?;
+    let stats = Stats::new(&groups);
+
+    if csv {
+        print!("{}", stats.to_csv());
+    } else {
+        print!("{}", stats);
+    }
+
+    Ok(true)
+}
+
+fn main() {
+    let mut raw_args = env::args().skip(1).peekable();
+    let command: Option<fn(_) -> _> = match raw_args.peek().map(|arg| &arg[..]) {
+        Some("verify") => Some(verify),
+        Some("passports") => Some(passports),
+        Some("customs") => Some(customs),
+        _ => None,
+    };
+    if let Some(command) = command {
+        match command(raw_args.skip(1)) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(2);
+            }
+        }
+    }
+
+    let args = parse_args(raw_args).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2);
+    });
+    if args.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let selected: Vec<&Day> = match args.day {
+        Some(number) => match days::get(number) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("day {} is not implemented", number);
+                process::exit(1);
+            }
+        },
+        None => days::DAYS.iter().collect(),
+    };
+
+    let mut reports = vec![];
+    let mut failed = false;
+    for day in selected {
+        let source = args
+            .input
+            .clone()
+            .unwrap_or(InputSource::Embedded(day.number));
+
+        if let Some(report) = run(day, args.parts, source) {
+            if !args.time {
+                print_answers(day, &report);
+            }
+            reports.push((day, report));
+        } else {
+            failed = true;
+        }
+    }
+
+    if args.time {
+        print_times(&reports);
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::error::AocError;
+use std::fmt;
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid stored row by row, `(x, y)` with `(0, 0)` at the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// One row per line of `input`, each character turned into a cell by `cell`.
+    /// Fails on a character `cell` rejects or a line of a different width.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, AocError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let line_width = line.chars().count();
+            if *width.get_or_insert(line_width) != line_width {
+                return Err(AocError::parse(index + 1, line, "row is a different width"));
+            }
+
+            for c in line.chars() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(AocError::parse(
+                            index + 1,
+                            line,
+                            format!("unexpected character {:?}", c),
+                        ))
+                    }
+                }
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index(x, y).map(move |i| &mut self.cells[i])
+    }
+
+    /// The cell at `(x, y)` as if the grid repeated forever in every direction.
+    /// `None` only for an empty grid.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> Option<&T> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        self.get(x, y)
+    }
+
+    fn offset(&self, x: usize, y: usize, (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = (x as isize).checked_add(dx)?;
+        let y = (y as isize).checked_add(dy)?;
+        if x < 0 || y < 0 {
+            return None;
+        }
+        self.index(x as usize, y as usize)
+            .map(|_| (x as usize, y as usize))
+    }
+
+    /// Cells above, right, below and left of `(x, y)` that are inside the grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        OFFSETS_4
+            .iter()
+            .filter_map(move |&offset| self.offset(x, y, offset))
+            .map(move |(x, y)| ((x, y), &self.cells[y * self.width + x]))
+    }
+
+    /// The up to eight cells around `(x, y)`, diagonals included.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        OFFSETS_8
+            .iter()
+            .filter_map(move |&offset| self.offset(x, y, offset))
+            .map(move |(x, y)| ((x, y), &self.cells[y * self.width + x]))
+    }
+
+    /// Each row from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, and a zero width grid has no cells to split anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// Column `x` from top to bottom, empty if `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Every character as it is, e.g. a `.` and `#` map.
+    pub fn from_chars(input: &str) -> Result<Grid<char>, AocError> {
+        Grid::parse(input, Some)
+    }
+}
+
+/// Rows on their own lines, each cell written with its `Display`.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_test() {
+        let grid = Grid::parse("#..\n.#.", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&true), grid.get(1, 1));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(Some(&true), grid.get_wrapping(-2, -1));
+        assert_eq!(vec![&false, &true], grid.column(1).collect::<Vec<_>>());
+        let positions = |neighbours: &mut dyn Iterator<Item = ((usize, usize), &bool)>| {
+            neighbours.map(|(pos, _)| pos).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![(1, 0), (1, 1), (0, 1)],
+            positions(&mut grid.neighbours8(0, 0))
+        );
+        assert_eq!(vec![(2, 1), (1, 0)], positions(&mut grid.neighbours4(2, 0)));
+        assert_eq!(
+            "#..\n.#.\n",
+            grid.map(|&c| if c { '#' } else { '.' }).to_string()
+        );
+    }
+
+    #[test]
+    fn grid_errors_test() {
+        assert_eq!(
+            "line 2: row is a different width: \"..\"",
+            Grid::from_chars("...\n..").unwrap_err().to_string()
+        );
+        assert!(Grid::parse("ab", |c| c.to_digit(10)).is_err());
+    }
+}
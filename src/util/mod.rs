@@ -0,0 +1,205 @@
+use crate::error::AocError;
+use std::{
+    fs::File,
+    io::{prelude::*, BufReader},
+    path::Path,
+    str::FromStr,
+};
+
+mod grid;
+mod point;
+
+pub use grid::Grid;
+pub use point::Point;
+
+fn open(filename: &Path) -> Result<BufReader<File>, AocError> {
+    File::open(filename)
+        .map(BufReader::new)
+        .map_err(|err| AocError::io(filename, err))
+}
+
+/// Whole file as a string.
+pub fn read_file(filename: impl AsRef<Path>) -> Result<String, AocError> {
+    let filename = filename.as_ref();
+    let mut contents = String::new();
+    open(filename)?
+        .read_to_string(&mut contents)
+        .map_err(|err| AocError::io(filename, err))?;
+    Ok(contents)
+}
+
+/// Parse every line of `input`, failing on the first one that doesn't parse.
+pub fn parse_lines<T: FromStr>(input: &str) -> Result<Vec<T>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, l)| {
+            l.parse::<T>()
+                .map_err(|_| AocError::parse(index + 1, l, "could not parse value"))
+        })
+        .collect()
+}
+
+/// Lines read one at a time, so only the current line is held in memory.
+/// Works over a `File`, `stdin().lock()` or `&[u8]` alike.
+pub fn lines_iter<R: BufRead>(reader: R) -> impl Iterator<Item = Result<String, AocError>> {
+    reader.lines().map(|l| l.map_err(AocError::from))
+}
+
+/// Lines parsed one at a time, errors carry the 1-indexed line number.
+pub fn parsed_iter<T: FromStr, R: BufRead>(reader: R) -> impl Iterator<Item = Result<T, AocError>> {
+    lines_iter(reader).enumerate().map(|(index, l)| {
+        let l = l?;
+        l.parse::<T>()
+            .map_err(|_| AocError::parse(index + 1, &l, "could not parse value"))
+    })
+}
+
+/// Runs of non-blank lines read one group at a time, see [`Records`].
+pub fn blank_line_groups_iter<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = Result<Vec<String>, AocError>> {
+    Records::new(lines_iter(reader)).map(|record| record.map(|record| record.lines))
+}
+
+/// The blank-line separated records of `input`, e.g. the passports of day 4.
+pub fn records(input: &str) -> impl Iterator<Item = Record<&str>> {
+    // Lines of a string can't fail to read
+    Records::new(input.lines().map(Ok)).filter_map(Result::ok)
+}
+
+/// A run of non-blank lines, `first_line` counting from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record<T> {
+    pub first_line: usize,
+    pub lines: Vec<T>,
+}
+
+impl<T> Record<T> {
+    /// Each line with its line number in the input.
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &T)> {
+        (self.first_line..).zip(&self.lines)
+    }
+}
+
+/// Splits lines into records on blank lines. Any number of blank or
+/// whitespace-only lines separate records, so there's never an empty one,
+/// and a `\r` left over from CRLF line endings is dropped.
+pub struct Records<I> {
+    lines: I,
+    line_number: usize,
+}
+
+impl<I> Records<I> {
+    pub fn new(lines: I) -> Records<I> {
+        Records {
+            lines,
+            line_number: 0,
+        }
+    }
+}
+
+impl<I, T> Iterator for Records<I>
+where
+    I: Iterator<Item = Result<T, AocError>>,
+    T: AsRef<str> + TrimCr,
+{
+    type Item = Result<Record<T>, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = Record {
+            first_line: 0,
+            lines: vec![],
+        };
+
+        for line in &mut self.lines {
+            self.line_number += 1;
+
+            let line = match line {
+                Ok(line) => line.trim_cr(),
+                Err(err) => return Some(Err(err)),
+            };
+
+            if line.as_ref().trim().is_empty() {
+                if !record.lines.is_empty() {
+                    return Some(Ok(record));
+                }
+            } else {
+                if record.lines.is_empty() {
+                    record.first_line = self.line_number;
+                }
+                record.lines.push(line);
+            }
+        }
+
+        if record.lines.is_empty() {
+            None
+        } else {
+            Some(Ok(record))
+        }
+    }
+}
+
+/// Drops a trailing `\r`, for lines split on `\n` alone.
+pub trait TrimCr {
+    fn trim_cr(self) -> Self;
+}
+
+impl TrimCr for &str {
+    fn trim_cr(self) -> Self {
+        self.strip_suffix('\r').unwrap_or(self)
+    }
+}
+
+impl TrimCr for String {
+    fn trim_cr(mut self) -> Self {
+        if self.ends_with('\r') {
+            self.pop();
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsed_iter_test() {
+        let mut numbers = parsed_iter::<u32, _>("1\n2\nthree\n".as_bytes());
+        assert_eq!(1, numbers.next().unwrap().unwrap());
+        assert_eq!(2, numbers.next().unwrap().unwrap());
+        assert_eq!(
+            "line 3: could not parse value: \"three\"",
+            numbers.next().unwrap().unwrap_err().to_string()
+        );
+        assert!(numbers.next().is_none());
+    }
+
+    #[test]
+    fn blank_line_groups_iter_test() {
+        let groups = blank_line_groups_iter("\na\nb\n\n\n\nc\n\n".as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(vec![vec!["a", "b"], vec!["c"]], groups);
+    }
+
+    #[test]
+    fn records_test() {
+        let found: Vec<Record<&str>> = records("a\r\nb\r\n\r\n  \n\nc d\ne").collect();
+        assert_eq!(
+            vec![
+                Record {
+                    first_line: 1,
+                    lines: vec!["a", "b"]
+                },
+                Record {
+                    first_line: 6,
+                    lines: vec!["c d", "e"]
+                },
+            ],
+            found
+        );
+        assert_eq!(0, records("\n\n").count());
+    }
+}
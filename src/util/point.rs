@@ -0,0 +1,120 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A position or offset on a grid, `x` to the right and `y` down, as in
+/// [`Grid`](super::Grid).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const RIGHT: Point = Point::new(1, 0);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+
+    /// The four directions, clockwise from up.
+    pub const DIRECTIONS: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// Steps between the two points moving only up, down, left and right.
+    pub fn manhattan(self, other: Point) -> u64 {
+        (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
+    }
+
+    /// Turned about the origin by `quarter_turns` of 90°, clockwise as drawn
+    /// with `y` down. Negative turns go anticlockwise.
+    pub fn rotate(self, quarter_turns: i32) -> Point {
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => Point::new(-self.y, self.x),
+            2 => -self,
+            _ => Point::new(self.y, -self.x),
+        }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_test() {
+        let p = Point::new(3, -2);
+
+        assert_eq!(Point::new(4, -2), p + Point::RIGHT);
+        assert_eq!(Point::new(3, -1), p - Point::UP);
+        assert_eq!(Point::new(-6, 4), p * -2);
+        assert_eq!(7, p.manhattan(Point::new(-1, 1)));
+        assert_eq!(Point::DOWN, Point::RIGHT.rotate(1));
+        assert_eq!(Point::LEFT, Point::UP.rotate(-1));
+        assert_eq!(p, p.rotate(4));
+        assert_eq!(-p, p.rotate(2));
+        assert_eq!(
+            Point::DIRECTIONS.to_vec(),
+            Point::DIRECTIONS
+                .iter()
+                .map(|d| d.rotate(1).rotate(-1))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Point::DIRECTIONS[1], Point::DIRECTIONS[0].rotate(1));
+    }
+}
@@ -0,0 +1,43 @@
+use adventofcode::answers::{self, Manifest};
+use std::fs;
+
+#[test]
+fn answers_test() {
+    let dir = answers::data_dir();
+    let manifest = Manifest::load(dir.join("answers.toml")).unwrap();
+
+    let failures: Vec<String> = answers::verify(&manifest, &dir)
+        .iter()
+        .filter(|check| !check.passed())
+        .map(|check| check.to_string())
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// Every puzzle input and worked example has to be listed in answers.toml,
+// which is how a new fixture gets checked at all
+#[test]
+fn answers_manifest_test() {
+    let dir = answers::data_dir();
+    let manifest = Manifest::load(dir.join("answers.toml")).unwrap();
+
+    for sub_dir in &["", "examples"] {
+        for file in fs::read_dir(dir.join(sub_dir)).unwrap() {
+            let name = file.unwrap().file_name().into_string().unwrap();
+            let input = if sub_dir.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", sub_dir, name)
+            };
+
+            if name.ends_with(".txt") {
+                assert!(
+                    manifest.answers.iter().any(|entry| entry.input == input),
+                    "{} has no entry in answers.toml",
+                    input
+                );
+            }
+        }
+    }
+}